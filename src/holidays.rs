@@ -0,0 +1,173 @@
+//! Bank-holiday / market-closure awareness.
+//!
+//! Forex Factory's own "Bank Holiday" rows are recognized during parsing
+//! (see [`crate::scraper::CalendarParser`]), but its coverage is incomplete.
+//! [`HolidayCalendar`] lets a user supply additional closures from a JSON
+//! file, merged into a batch of already-fetched events at query time.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use chrono::{Datelike, NaiveDate, TimeZone, Utc};
+use color_eyre::{Result, eyre::eyre};
+use serde::{Deserialize, Serialize};
+
+use crate::types::{Currency, EconomicEvent, Impact};
+
+/// A single market closure for one currency on one date.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Holiday {
+    pub date: NaiveDate,
+    pub name: String,
+}
+
+/// Currency -> closures, loaded from a user-supplied JSON file shaped as
+/// `{"USD": [{"date": "2025-07-04", "name": "Independence Day"}], "JPY": [...]}`.
+/// New regions/currencies are added by editing the file alone, no code
+/// changes required.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HolidayCalendar(HashMap<Currency, Vec<Holiday>>);
+
+impl HolidayCalendar {
+    /// Load a holiday calendar from a JSON file.
+    pub fn load_from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| eyre!("Failed to read holiday calendar {}: {e}", path.display()))?;
+        Self::from_json(&contents)
+            .map_err(|e| eyre!("Failed to parse holiday calendar {}: {e}", path.display()))
+    }
+
+    /// Parse a holiday calendar from a JSON string.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let calendar: HashMap<Currency, Vec<Holiday>> =
+            serde_json::from_str(json).map_err(|e| eyre!("Invalid holiday calendar JSON: {e}"))?;
+        Ok(Self(calendar))
+    }
+
+    /// Closures recorded for `currency` on `date`, if any.
+    fn holidays_on(&self, currency: Currency, date: NaiveDate) -> impl Iterator<Item = &Holiday> {
+        self.0
+            .get(&currency)
+            .into_iter()
+            .flatten()
+            .filter(move |h| h.date == date)
+    }
+
+    /// Merge this calendar's closures into `events`: scraped events for a
+    /// closed currency/date are tagged `is_holiday`, and any closure with no
+    /// matching scraped row gets a synthetic holiday event appended so
+    /// callers can still see the market was shut.
+    pub fn annotate(&self, mut events: Vec<EconomicEvent>) -> Vec<EconomicEvent> {
+        for event in &mut events {
+            let date = event.datetime.date_naive();
+            if self.holidays_on(event.currency, date).next().is_some() {
+                event.is_holiday = true;
+                event.affected_currencies = Some(vec![event.currency]);
+            }
+        }
+
+        for (&currency, holidays) in &self.0 {
+            for holiday in holidays {
+                let already_present = events
+                    .iter()
+                    .any(|e| e.currency == currency && e.datetime.date_naive() == holiday.date);
+                if !already_present {
+                    events.push(synthetic_holiday_event(currency, holiday));
+                }
+            }
+        }
+
+        events
+    }
+}
+
+/// Build a placeholder `EconomicEvent` representing a closure that the
+/// scraper didn't itself surface as a row.
+fn synthetic_holiday_event(currency: Currency, holiday: &Holiday) -> EconomicEvent {
+    EconomicEvent {
+        name: holiday.name.clone(),
+        currency,
+        impact: Impact::Low,
+        datetime: Utc
+            .with_ymd_and_hms(holiday.date.year(), holiday.date.month(), holiday.date.day(), 0, 0, 0)
+            .unwrap(),
+        actual: None,
+        forecast: None,
+        previous: None,
+        is_holiday: true,
+        affected_currencies: Some(vec![currency]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_event(currency: Currency, day: u32) -> EconomicEvent {
+        EconomicEvent {
+            name: "Test Event".to_string(),
+            currency,
+            impact: Impact::High,
+            datetime: Utc.with_ymd_and_hms(2025, 7, day, 12, 0, 0).unwrap(),
+            actual: None,
+            forecast: None,
+            previous: None,
+            is_holiday: false,
+            affected_currencies: None,
+        }
+    }
+
+    #[test]
+    fn test_from_json_parses_per_currency_closures() {
+        let json = r#"{"USD": [{"date": "2025-07-04", "name": "Independence Day"}]}"#;
+        let calendar = HolidayCalendar::from_json(json).unwrap();
+        assert_eq!(
+            calendar.holidays_on(Currency::Usd, NaiveDate::from_ymd_opt(2025, 7, 4).unwrap()).count(),
+            1
+        );
+        assert_eq!(
+            calendar.holidays_on(Currency::Usd, NaiveDate::from_ymd_opt(2025, 7, 5).unwrap()).count(),
+            0
+        );
+    }
+
+    #[test]
+    fn test_from_json_rejects_invalid_json() {
+        assert!(HolidayCalendar::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_annotate_tags_matching_scraped_event() {
+        let json = r#"{"USD": [{"date": "2025-07-04", "name": "Independence Day"}]}"#;
+        let calendar = HolidayCalendar::from_json(json).unwrap();
+        let events = vec![sample_event(Currency::Usd, 4)];
+
+        let annotated = calendar.annotate(events);
+        assert_eq!(annotated.len(), 1);
+        assert!(annotated[0].is_holiday);
+        assert_eq!(annotated[0].affected_currencies, Some(vec![Currency::Usd]));
+    }
+
+    #[test]
+    fn test_annotate_appends_synthetic_event_for_unmatched_closure() {
+        let json = r#"{"JPY": [{"date": "2025-07-04", "name": "Some Closure"}]}"#;
+        let calendar = HolidayCalendar::from_json(json).unwrap();
+        let events = vec![sample_event(Currency::Usd, 4)];
+
+        let annotated = calendar.annotate(events);
+        assert_eq!(annotated.len(), 2);
+        let synthetic = annotated.iter().find(|e| e.currency == Currency::Jpy).unwrap();
+        assert!(synthetic.is_holiday);
+        assert_eq!(synthetic.name, "Some Closure");
+    }
+
+    #[test]
+    fn test_annotate_leaves_unrelated_events_untouched() {
+        let calendar = HolidayCalendar::default();
+        let events = vec![sample_event(Currency::Usd, 4)];
+        let annotated = calendar.annotate(events);
+        assert_eq!(annotated.len(), 1);
+        assert!(!annotated[0].is_holiday);
+    }
+}
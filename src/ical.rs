@@ -0,0 +1,262 @@
+//! RFC 5545 iCalendar (.ics) rendering for economic events.
+
+use chrono::{DateTime, NaiveTime, Utc};
+
+use crate::types::EconomicEvent;
+
+/// Duration assumed for events, since Forex Factory only publishes a start time.
+const DEFAULT_DURATION_MINUTES: i64 = 30;
+
+/// Render a set of economic events as an RFC 5545 `VCALENDAR` feed.
+///
+/// Each event becomes one `VEVENT`. Text fields are escaped and long lines are
+/// folded per the spec so the output is accepted by Google Calendar,
+/// Thunderbird, and other compliant clients.
+pub fn to_ics(events: &[EconomicEvent]) -> String {
+    let now = Utc::now();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//forex-factory-calendar-mcp//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for event in events {
+        lines.extend(render_event(event, now));
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut out = lines
+        .iter()
+        .flat_map(|line| fold_line(line))
+        .collect::<Vec<_>>()
+        .join("\r\n");
+    out.push_str("\r\n");
+    out
+}
+
+/// Render a single event as a `BEGIN:VEVENT` .. `END:VEVENT` block (unfolded).
+fn render_event(event: &EconomicEvent, now: DateTime<Utc>) -> Vec<String> {
+    let mut lines = vec!["BEGIN:VEVENT".to_string()];
+
+    lines.push(format!("UID:{}", event_uid(event)));
+    lines.push(format!("DTSTAMP:{}", now.format("%Y%m%dT%H%M%SZ")));
+
+    // Holidays are always all-day; other rows with no real time (e.g. "All
+    // Day" / "Tentative") fall back to midnight when parsed, which we also
+    // treat as the all-day signal. `is_holiday` is checked explicitly since
+    // the parser carries the previous row's time forward for "All Day" rows,
+    // which isn't always exactly midnight.
+    if event.is_holiday || event.datetime.time() == NaiveTime::MIN {
+        let date = event.datetime.format("%Y%m%d");
+        lines.push(format!("DTSTART;VALUE=DATE:{date}"));
+    } else {
+        // The trailing "Z" form (RFC 5545 §3.3.5) marks these as UTC times
+        // directly, so no VTIMEZONE/TZID is needed to disambiguate them.
+        let start = event.datetime.with_timezone(&Utc);
+        let end = start + chrono::Duration::minutes(DEFAULT_DURATION_MINUTES);
+        lines.push(format!("DTSTART:{}", start.format("%Y%m%dT%H%M%SZ")));
+        lines.push(format!("DTEND:{}", end.format("%Y%m%dT%H%M%SZ")));
+    }
+
+    lines.push(format!(
+        "SUMMARY:{}",
+        escape_text(&format!(
+            "{} {} {}",
+            event.impact.emoji(),
+            event.currency,
+            event.name
+        ))
+    ));
+
+    let description = [
+        event.actual.as_ref().map(|v| format!("Actual: {v}")),
+        event.forecast.as_ref().map(|v| format!("Forecast: {v}")),
+        event.previous.as_ref().map(|v| format!("Previous: {v}")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>()
+    .join("\n");
+
+    if !description.is_empty() {
+        lines.push(format!("DESCRIPTION:{}", escape_text(&description)));
+    }
+
+    lines.push("END:VEVENT".to_string());
+    lines
+}
+
+/// Derive a stable UID from the event's identity so re-exporting the same
+/// event (e.g. after a forecast update) keeps the same calendar entry.
+fn event_uid(event: &EconomicEvent) -> String {
+    format!("{}@forexfactory-calendar-mcp", event.id())
+}
+
+/// Escape text per RFC 5545 §3.3.11: backslashes, commas, semicolons, and
+/// newlines must be backslash-escaped inside a property value.
+fn escape_text(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace(';', "\\;")
+        .replace('\n', "\\n")
+}
+
+/// Fold a single logical line into RFC 5545 §3.1 physical lines: no physical
+/// line may exceed 75 octets, and continuations start with a single space.
+fn fold_line(line: &str) -> Vec<String> {
+    if line.len() <= 75 {
+        return vec![line.to_string()];
+    }
+
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut first = true;
+    while start < line.len() {
+        let limit = if first { 75 } else { 74 };
+        let mut end = (start + limit).min(line.len());
+        while end > start && !line.is_char_boundary(end) {
+            end -= 1;
+        }
+        let chunk = &line[start..end];
+        out.push(if first {
+            chunk.to_string()
+        } else {
+            format!(" {chunk}")
+        });
+        start = end;
+        first = false;
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Impact;
+    use chrono::{TimeZone, Utc};
+
+    fn sample_event() -> EconomicEvent {
+        EconomicEvent {
+            name: "Non-Farm Payrolls".to_string(),
+            currency: crate::types::Currency::Usd,
+            impact: Impact::High,
+            datetime: Utc.with_ymd_and_hms(2025, 6, 6, 12, 30, 0).unwrap(),
+            actual: Some("240K".to_string()),
+            forecast: Some("190K".to_string()),
+            previous: Some("175K".to_string()),
+            is_holiday: false,
+            affected_currencies: None,
+        }
+    }
+
+    #[test]
+    fn test_to_ics_wraps_in_vcalendar() {
+        let ics = to_ics(&[sample_event()]);
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.trim_end().ends_with("END:VCALENDAR"));
+        assert!(ics.contains("BEGIN:VEVENT\r\n"));
+        assert!(ics.contains("SUMMARY:\u{1f534} USD Non-Farm Payrolls"));
+    }
+
+    #[test]
+    fn test_summary_uses_impact_emoji() {
+        let mut event = sample_event();
+        event.impact = Impact::Low;
+        let ics = to_ics(&[event]);
+        assert!(ics.contains("SUMMARY:\u{1f7e1} USD Non-Farm Payrolls"));
+    }
+
+    /// Unfold RFC 5545 §3.1 physical lines back into logical lines: a CRLF
+    /// followed by a single space or tab is a continuation, not a line break.
+    fn unfold(ics: &str) -> Vec<String> {
+        let mut lines: Vec<String> = Vec::new();
+        for physical in ics.split("\r\n").filter(|l| !l.is_empty()) {
+            if let Some(stripped) = physical.strip_prefix(' ') {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(stripped);
+                    continue;
+                }
+            }
+            lines.push(physical.to_string());
+        }
+        lines
+    }
+
+    #[test]
+    fn test_to_ics_round_trips_as_well_formed_icalendar() {
+        let ics = to_ics(&[sample_event()]);
+        let lines = unfold(&ics);
+
+        // Every BEGIN has a matching END, properly nested.
+        let mut stack = Vec::new();
+        for line in &lines {
+            if let Some(component) = line.strip_prefix("BEGIN:") {
+                stack.push(component.to_string());
+            } else if let Some(component) = line.strip_prefix("END:") {
+                assert_eq!(stack.pop().as_deref(), Some(component));
+            } else {
+                // Every other content line is a "NAME:value" or "NAME;param=...:value" pair.
+                assert!(line.contains(':'), "malformed content line: {line}");
+            }
+        }
+        assert!(stack.is_empty(), "unbalanced BEGIN/END components");
+
+        assert!(lines.contains(&"VERSION:2.0".to_string()));
+        assert!(lines.iter().any(|l| l.starts_with("UID:")));
+        assert!(
+            lines
+                .iter()
+                .any(|l| l.starts_with("SUMMARY:") || l.starts_with("DTSTART"))
+        );
+    }
+
+    #[test]
+    fn test_all_day_event_uses_date_value() {
+        let mut event = sample_event();
+        event.datetime = Utc.with_ymd_and_hms(2025, 6, 6, 0, 0, 0).unwrap();
+        let ics = to_ics(&[event]);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250606"));
+    }
+
+    #[test]
+    fn test_holiday_event_uses_date_value_even_with_carried_over_time() {
+        let mut event = sample_event();
+        event.is_holiday = true;
+        event.datetime = Utc.with_ymd_and_hms(2025, 6, 6, 12, 30, 0).unwrap();
+        let ics = to_ics(&[event]);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20250606"));
+        assert!(!ics.contains("DTSTART:20250606"));
+    }
+
+    #[test]
+    fn test_escape_text() {
+        assert_eq!(escape_text("a, b; c\nd"), "a\\, b\\; c\\nd");
+    }
+
+    #[test]
+    fn test_fold_line_short_line_unchanged() {
+        assert_eq!(fold_line("SUMMARY:short"), vec!["SUMMARY:short".to_string()]);
+    }
+
+    #[test]
+    fn test_fold_line_splits_long_line() {
+        let long_value = "x".repeat(100);
+        let line = format!("DESCRIPTION:{long_value}");
+        let folded = fold_line(&line);
+        assert!(folded.len() > 1);
+        assert!(folded[0].len() <= 75);
+        for continuation in &folded[1..] {
+            assert!(continuation.starts_with(' '));
+        }
+        // Rejoining (minus the folding whitespace) should reproduce the line.
+        let rejoined: String = folded
+            .iter()
+            .enumerate()
+            .map(|(i, l)| if i == 0 { l.clone() } else { l[1..].to_string() })
+            .collect();
+        assert_eq!(rejoined, line);
+    }
+}
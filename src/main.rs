@@ -1,7 +1,11 @@
+mod holidays;
+mod ical;
 mod mcp;
 mod scraper;
+mod schedule;
 mod service;
 mod types;
+mod web;
 
 use color_eyre::Result;
 use rmcp::ServiceExt;
@@ -23,6 +27,11 @@ async fn main() -> Result<()> {
         .with_writer(std::io::stderr)
         .init();
 
+    if let Some(addr) = http_listen_addr() {
+        info!("Starting HTTP calendar server on {addr}...");
+        return web::serve(&addr).await;
+    }
+
     info!("Forex Factory Calendar MCP Server starting...");
 
     // Create the server
@@ -40,3 +49,19 @@ async fn main() -> Result<()> {
     info!("Server shutting down");
     Ok(())
 }
+
+/// Determine the HTTP listen address, if opted into via `--http <addr>`
+/// (or `--http=<addr>`) or the `FOREX_CALENDAR_HTTP_ADDR` environment
+/// variable. Returns `None` to keep the default stdio MCP transport.
+fn http_listen_addr() -> Option<String> {
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--http" {
+            return args.next();
+        }
+        if let Some(addr) = arg.strip_prefix("--http=") {
+            return Some(addr.to_string());
+        }
+    }
+    std::env::var("FOREX_CALENDAR_HTTP_ADDR").ok()
+}
@@ -0,0 +1,4 @@
+mod server;
+mod tools;
+
+pub use server::ForexCalendarServer;
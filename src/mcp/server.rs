@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use chrono_tz::Tz;
 use rmcp::{
     ErrorData as McpError, ServerHandler,
     handler::server::{router::tool::ToolRouter, wrapper::Parameters},
@@ -9,15 +10,22 @@ use rmcp::{
 use tokio::sync::RwLock;
 use tracing::{error, info};
 
+use crate::schedule::{HighImpactWatcher, ScheduleRule};
 use crate::service::CalendarService;
-use crate::types::{EventQuery, Impact};
+use crate::types::{EventQuery, Impact, find_by_id, group_by_currency};
 
-use super::tools::{EventResult, QueryEventsParams, WeekAroundParams};
+use super::tools::{
+    CheckScheduleParams, DayEvents, EventByIdParams, EventResult, FormatParams, OutputFormat,
+    QueryEventsParams, ScheduleCheckResult, WeekAroundParams, render_agenda_text, render_markdown,
+};
 
 /// MCP Server for Forex Factory Calendar
 #[derive(Clone)]
 pub struct ForexCalendarServer {
     service: Arc<RwLock<Option<CalendarService>>>,
+    /// Tracks previously-seen high-impact events across `check_schedule`
+    /// calls for the lifetime of this server instance.
+    schedule_watcher: Arc<RwLock<HighImpactWatcher>>,
     tool_router: ToolRouter<Self>,
 }
 
@@ -27,10 +35,63 @@ impl ForexCalendarServer {
     pub fn new() -> Self {
         Self {
             service: Arc::new(RwLock::new(None)),
+            schedule_watcher: Arc::new(RwLock::new(HighImpactWatcher::new())),
             tool_router: Self::tool_router(),
         }
     }
 
+    /// Build an `EventQuery` from the shared currency/date/impact params used
+    /// by `query_events` and `export_ics`.
+    fn build_query(params: &QueryEventsParams) -> EventQuery {
+        let mut query = EventQuery::new();
+
+        let currencies = params.parse_currencies();
+        if !currencies.is_empty() {
+            query = query.with_currencies(currencies);
+        }
+
+        if let Some(from) = params.parse_from_date() {
+            if let Some(to) = params.parse_to_date() {
+                query = query.with_date_range(from, to);
+            } else {
+                query = query.with_date_range(from, from);
+            }
+        }
+
+        if let Some(impact) = params.parse_min_impact() {
+            query = query.with_min_impact(impact);
+        }
+
+        if let Some(tz) = params.parse_display_tz() {
+            query = query.with_display_tz(tz);
+        }
+
+        query = query.with_exclude_holidays(params.parse_exclude_holidays());
+
+        query
+    }
+
+    /// Render events as JSON or Markdown depending on the requested format,
+    /// with times projected into `tz`.
+    fn render_events(
+        events: Vec<crate::types::EconomicEvent>,
+        format: OutputFormat,
+        tz: Tz,
+    ) -> Result<String, McpError> {
+        match format {
+            OutputFormat::Json => {
+                let results: Vec<EventResult> = events
+                    .into_iter()
+                    .map(|e| EventResult::from_event(e, tz))
+                    .collect();
+                serde_json::to_string_pretty(&results).map_err(|e| {
+                    McpError::internal_error(format!("Failed to serialize results: {e}"), None)
+                })
+            }
+            OutputFormat::Markdown => Ok(render_markdown(events, tz)),
+        }
+    }
+
     /// Get or initialize the calendar service
     async fn get_service(&self) -> Result<(), McpError> {
         let mut service_guard = self.service.write().await;
@@ -64,7 +125,7 @@ impl Default for ForexCalendarServer {
 impl ForexCalendarServer {
     /// Query economic events from Forex Factory calendar.
     #[tool(
-        description = "Query economic events from Forex Factory calendar. Supports filtering by currency (e.g., 'USD', 'AUD/CHF'), date range (YYYY-MM-DD format), and minimum impact level ('low', 'medium', 'high' or 1-3)."
+        description = "Query economic events from Forex Factory calendar. Supports filtering by currency (e.g., 'USD', 'AUD/CHF'), date range (YYYY-MM-DD format), and minimum impact level ('low', 'medium', 'high' or 1-3). Pass format: \"markdown\" for a human-readable agenda instead of JSON."
     )]
     async fn query_events(
         &self,
@@ -78,33 +139,13 @@ impl ForexCalendarServer {
             .ok_or_else(|| McpError::internal_error("Calendar service not initialized", None))?;
 
         // Build query from params
-        let mut query = EventQuery::new();
-
-        let currencies = params.parse_currencies();
-        if !currencies.is_empty() {
-            query = query.with_currencies(currencies);
-        }
-
-        if let Some(from) = params.parse_from_date() {
-            if let Some(to) = params.parse_to_date() {
-                query = query.with_date_range(from, to);
-            } else {
-                query = query.with_date_range(from, from);
-            }
-        }
-
-        if let Some(impact) = params.parse_min_impact() {
-            query = query.with_min_impact(impact);
-        }
+        let query = Self::build_query(&params);
+        let format = params.parse_format();
+        let tz = params.parse_display_tz().unwrap_or(Tz::UTC);
 
         // Execute query
         match service.query_events(&query).await {
-            Ok(events) => {
-                let results: Vec<EventResult> = events.into_iter().map(Into::into).collect();
-                serde_json::to_string_pretty(&results).map_err(|e| {
-                    McpError::internal_error(format!("Failed to serialize results: {e}"), None)
-                })
-            }
+            Ok(events) => Self::render_events(events, format, tz),
             Err(e) => {
                 error!("Query failed: {e}");
                 Err(McpError::internal_error(format!("Query failed: {e}"), None))
@@ -114,7 +155,7 @@ impl ForexCalendarServer {
 
     /// Get events for the week around a specific date.
     #[tool(
-        description = "Get economic events for the week around a specific date. Returns events 3 days before and after the specified date."
+        description = "Get economic events for the week around a specific date. Returns events 3 days before and after the specified date. Pass format: \"markdown\" for a human-readable agenda instead of JSON."
     )]
     async fn get_week_around(
         &self,
@@ -135,6 +176,10 @@ impl ForexCalendarServer {
         // Build query
         let mut query = EventQuery::new().with_week_around(date);
 
+        if let Some(tz) = params.parse_display_tz() {
+            query = query.with_display_tz(tz);
+        }
+
         if let Some(ref currencies) = params.currencies {
             let parsed: Vec<String> = currencies
                 .split(['/', ',', '-', ' '])
@@ -162,17 +207,15 @@ impl ForexCalendarServer {
         match service.get_week_events_for(date).await {
             Ok(events) => {
                 let min_impact = query.min_impact.unwrap_or(Impact::Low);
-                let filtered: Vec<EventResult> = events
+                let filtered: Vec<_> = events
                     .into_iter()
                     .filter(|e| e.meets_impact(min_impact))
                     .filter(|e| e.matches_currencies(&query.currencies))
                     .filter(|e| query.datetime_in_range(&e.datetime))
-                    .map(Into::into)
                     .collect();
 
-                serde_json::to_string_pretty(&filtered).map_err(|e| {
-                    McpError::internal_error(format!("Failed to serialize results: {e}"), None)
-                })
+                let tz = params.parse_display_tz().unwrap_or(Tz::UTC);
+                Self::render_events(filtered, params.parse_format(), tz)
             }
             Err(e) => {
                 error!("Failed to get week events: {e}");
@@ -185,8 +228,13 @@ impl ForexCalendarServer {
     }
 
     /// Get today's economic events.
-    #[tool(description = "Get all economic events scheduled for today.")]
-    async fn get_today_events(&self) -> Result<String, McpError> {
+    #[tool(
+        description = "Get all economic events scheduled for today. Optionally pass format: \"markdown\" for a human-readable agenda."
+    )]
+    async fn get_today_events(
+        &self,
+        Parameters(params): Parameters<FormatParams>,
+    ) -> Result<String, McpError> {
         self.get_service().await?;
 
         let service_guard = self.service.read().await;
@@ -196,10 +244,8 @@ impl ForexCalendarServer {
 
         match service.get_today_events().await {
             Ok(events) => {
-                let results: Vec<EventResult> = events.into_iter().map(Into::into).collect();
-                serde_json::to_string_pretty(&results).map_err(|e| {
-                    McpError::internal_error(format!("Failed to serialize results: {e}"), None)
-                })
+                let tz = params.parse_display_tz().unwrap_or(Tz::UTC);
+                Self::render_events(events, params.parse_format(), tz)
             }
             Err(e) => {
                 error!("Failed to get today's events: {e}");
@@ -212,8 +258,13 @@ impl ForexCalendarServer {
     }
 
     /// Get this week's economic events.
-    #[tool(description = "Get all economic events scheduled for the current week.")]
-    async fn get_week_events(&self) -> Result<String, McpError> {
+    #[tool(
+        description = "Get all economic events scheduled for the current week. Optionally pass format: \"markdown\" for a human-readable agenda."
+    )]
+    async fn get_week_events(
+        &self,
+        Parameters(params): Parameters<FormatParams>,
+    ) -> Result<String, McpError> {
         self.get_service().await?;
 
         let service_guard = self.service.read().await;
@@ -223,10 +274,8 @@ impl ForexCalendarServer {
 
         match service.get_week_events().await {
             Ok(events) => {
-                let results: Vec<EventResult> = events.into_iter().map(Into::into).collect();
-                serde_json::to_string_pretty(&results).map_err(|e| {
-                    McpError::internal_error(format!("Failed to serialize results: {e}"), None)
-                })
+                let tz = params.parse_display_tz().unwrap_or(Tz::UTC);
+                Self::render_events(events, params.parse_format(), tz)
             }
             Err(e) => {
                 error!("Failed to get week events: {e}");
@@ -237,6 +286,230 @@ impl ForexCalendarServer {
             }
         }
     }
+
+    /// Get events grouped into day-by-day agenda buckets.
+    #[tool(
+        description = "Query economic events from Forex Factory calendar, like query_events, but grouped into day buckets (with weekday and date) for a readable agenda view."
+    )]
+    async fn get_agenda(
+        &self,
+        Parameters(params): Parameters<QueryEventsParams>,
+    ) -> Result<String, McpError> {
+        self.get_service().await?;
+
+        let service_guard = self.service.read().await;
+        let service = service_guard
+            .as_ref()
+            .ok_or_else(|| McpError::internal_error("Calendar service not initialized", None))?;
+
+        let query = Self::build_query(&params);
+        let tz = query.display_tz.unwrap_or(Tz::UTC);
+
+        match service.get_agenda(&query).await {
+            Ok(days) => {
+                let agenda: Vec<DayEvents> = days
+                    .into_iter()
+                    .map(|day| DayEvents::from_day(day, tz))
+                    .collect();
+                serde_json::to_string_pretty(&agenda).map_err(|e| {
+                    McpError::internal_error(format!("Failed to serialize results: {e}"), None)
+                })
+            }
+            Err(e) => {
+                error!("Agenda query failed: {e}");
+                Err(McpError::internal_error(
+                    format!("Agenda query failed: {e}"),
+                    None,
+                ))
+            }
+        }
+    }
+
+    /// Get events grouped into a plain-text, chat-friendly agenda.
+    #[tool(
+        description = "Query economic events from Forex Factory calendar, like query_events, but rendered as a plain-text agenda grouped by day with date headers. Pass show_gaps: true to list empty days within the query range instead of skipping them."
+    )]
+    async fn get_text_agenda(
+        &self,
+        Parameters(params): Parameters<QueryEventsParams>,
+    ) -> Result<String, McpError> {
+        self.get_service().await?;
+
+        let service_guard = self.service.read().await;
+        let service = service_guard
+            .as_ref()
+            .ok_or_else(|| McpError::internal_error("Calendar service not initialized", None))?;
+
+        let query = Self::build_query(&params);
+        let tz = query.display_tz.unwrap_or(Tz::UTC);
+        let range = match (query.from_date, query.to_date) {
+            (Some(from), Some(to)) if params.parse_show_gaps() => Some((from, to)),
+            _ => None,
+        };
+
+        match service.query_events(&query).await {
+            Ok(events) => Ok(render_agenda_text(events, tz, range)),
+            Err(e) => {
+                error!("Text agenda query failed: {e}");
+                Err(McpError::internal_error(
+                    format!("Text agenda query failed: {e}"),
+                    None,
+                ))
+            }
+        }
+    }
+
+    /// Look up a single event by its stable id.
+    #[tool(
+        description = "Look up a single economic event by the stable `id` returned on query_events/get_agenda results. Accepts the same currency/date/impact filters as query_events to narrow the window searched."
+    )]
+    async fn get_event_by_id(
+        &self,
+        Parameters(params): Parameters<EventByIdParams>,
+    ) -> Result<String, McpError> {
+        self.get_service().await?;
+
+        let service_guard = self.service.read().await;
+        let service = service_guard
+            .as_ref()
+            .ok_or_else(|| McpError::internal_error("Calendar service not initialized", None))?;
+
+        let query = Self::build_query(&params.as_query_params());
+        let tz = query.display_tz.unwrap_or(Tz::UTC);
+
+        match service.query_events(&query).await {
+            Ok(events) => match find_by_id(&events, &params.id) {
+                Some(event) => serde_json::to_string_pretty(&EventResult::from_event(
+                    event.clone(),
+                    tz,
+                ))
+                .map_err(|e| {
+                    McpError::internal_error(format!("Failed to serialize result: {e}"), None)
+                }),
+                None => Err(McpError::invalid_params(
+                    format!("No event found with id '{}'", params.id),
+                    None,
+                )),
+            },
+            Err(e) => {
+                error!("Lookup by id failed: {e}");
+                Err(McpError::internal_error(format!("Lookup by id failed: {e}"), None))
+            }
+        }
+    }
+
+    /// Get events grouped by currency.
+    #[tool(
+        description = "Query economic events from Forex Factory calendar, like query_events, but grouped by currency. Accepts the same currency/date/impact filters as query_events."
+    )]
+    async fn get_events_by_currency(
+        &self,
+        Parameters(params): Parameters<QueryEventsParams>,
+    ) -> Result<String, McpError> {
+        self.get_service().await?;
+
+        let service_guard = self.service.read().await;
+        let service = service_guard
+            .as_ref()
+            .ok_or_else(|| McpError::internal_error("Calendar service not initialized", None))?;
+
+        let query = Self::build_query(&params);
+        let tz = query.display_tz.unwrap_or(Tz::UTC);
+
+        match service.query_events(&query).await {
+            Ok(events) => {
+                let groups: std::collections::HashMap<String, Vec<EventResult>> =
+                    group_by_currency(events)
+                        .into_iter()
+                        .map(|(currency, events)| {
+                            (
+                                currency.to_string(),
+                                events
+                                    .into_iter()
+                                    .map(|e| EventResult::from_event(e, tz))
+                                    .collect(),
+                            )
+                        })
+                        .collect();
+                serde_json::to_string_pretty(&groups).map_err(|e| {
+                    McpError::internal_error(format!("Failed to serialize results: {e}"), None)
+                })
+            }
+            Err(e) => {
+                error!("Grouped query failed: {e}");
+                Err(McpError::internal_error(format!("Grouped query failed: {e}"), None))
+            }
+        }
+    }
+
+    /// Check a systemd.time-style schedule rule for new or changed
+    /// high-impact events.
+    #[tool(
+        description = "Check a systemd.time-style recurrence rule (e.g. \"Mon..Fri 08:30\", \"*:00\", \"7..17/2:00\") against the current week's events. Returns events that are newly seen or whose actual value has changed since this tool was last called, restricted to min_impact (defaults to \"high\"), plus the rule's next trigger time so a caller (e.g. a cron job or systemd timer) knows when to call again."
+    )]
+    async fn check_schedule(
+        &self,
+        Parameters(params): Parameters<CheckScheduleParams>,
+    ) -> Result<String, McpError> {
+        let rule = ScheduleRule::parse(&params.schedule)
+            .map_err(|e| McpError::invalid_params(format!("Invalid schedule: {e}"), None))?;
+        let next_trigger = rule
+            .next_trigger(chrono::Local::now())
+            .map_err(|e| McpError::invalid_params(format!("Invalid schedule: {e}"), None))?;
+
+        self.get_service().await?;
+
+        let service_guard = self.service.read().await;
+        let service = service_guard
+            .as_ref()
+            .ok_or_else(|| McpError::internal_error("Calendar service not initialized", None))?;
+
+        let min_impact = params.parse_min_impact().unwrap_or(Impact::High);
+
+        match service.get_week_events().await {
+            Ok(events) => {
+                let mut watcher = self.schedule_watcher.write().await;
+                let changes = watcher.check(events, min_impact);
+                let result = ScheduleCheckResult::from_changes(changes, next_trigger, Tz::UTC);
+                serde_json::to_string_pretty(&result).map_err(|e| {
+                    McpError::internal_error(format!("Failed to serialize result: {e}"), None)
+                })
+            }
+            Err(e) => {
+                error!("Schedule check failed: {e}");
+                Err(McpError::internal_error(format!("Schedule check failed: {e}"), None))
+            }
+        }
+    }
+
+    /// Export economic events as an iCalendar (.ics) feed.
+    #[tool(
+        description = "Export economic events from Forex Factory as an RFC 5545 iCalendar (.ics) feed, suitable for subscribing in Google Calendar, Thunderbird, or Outlook. Accepts the same currency, date range, and minimum impact filters as query_events."
+    )]
+    async fn export_ics(
+        &self,
+        Parameters(params): Parameters<QueryEventsParams>,
+    ) -> Result<String, McpError> {
+        self.get_service().await?;
+
+        let service_guard = self.service.read().await;
+        let service = service_guard
+            .as_ref()
+            .ok_or_else(|| McpError::internal_error("Calendar service not initialized", None))?;
+
+        let query = Self::build_query(&params);
+
+        match service.export_ics(&query).await {
+            Ok(ics) => Ok(ics),
+            Err(e) => {
+                error!("Failed to export iCalendar feed: {e}");
+                Err(McpError::internal_error(
+                    format!("Failed to export iCalendar feed: {e}"),
+                    None,
+                ))
+            }
+        }
+    }
 }
 
 impl ServerHandler for ForexCalendarServer {
@@ -252,7 +525,12 @@ impl ServerHandler for ForexCalendarServer {
             instructions: Some(
                 "Query economic events from Forex Factory calendar. \
                  Use query_events for filtered queries, get_week_around for date-centered queries, \
-                 or get_today_events/get_week_events for quick access to current events."
+                 get_today_events/get_week_events for quick access to current events, \
+                 get_agenda for a day-grouped view, get_text_agenda for a plain-text chat-friendly \
+                 agenda, get_events_by_currency for a currency-grouped view, get_event_by_id to \
+                 look up a single event by its stable id, check_schedule to poll a \
+                 systemd.time-style recurrence rule for new or changed high-impact events, or \
+                 export_ics to get a subscribable iCalendar feed."
                     .to_string(),
             ),
             capabilities: ServerCapabilities::builder().enable_tools().build(),
@@ -1,4 +1,5 @@
-use chrono::NaiveDate;
+use chrono::{Datelike, Duration, Local, NaiveDate, Weekday};
+use chrono_tz::Tz;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -21,6 +22,164 @@ pub struct QueryEventsParams {
     /// Minimum impact level: "low", "medium", "high" or 1-3 stars
     #[serde(default)]
     pub min_impact: Option<String>,
+
+    /// Output format: "json" (default) or "markdown"
+    #[serde(default)]
+    pub format: Option<String>,
+
+    /// IANA timezone to display event times/dates in, e.g. "America/New_York"
+    /// (defaults to UTC)
+    #[serde(default)]
+    pub display_tz: Option<String>,
+
+    /// Drop bank-holiday/market-closure events from the results (defaults to
+    /// `false`, i.e. holidays are included)
+    #[serde(default)]
+    pub exclude_holidays: Option<bool>,
+
+    /// In the text agenda view, show empty days within the query range
+    /// instead of collapsing them out (defaults to `false`)
+    #[serde(default)]
+    pub show_gaps: Option<bool>,
+}
+
+/// Parameters for looking up a single event by its stable id within a query
+/// window, narrowed by the same currency/date/impact filters as
+/// `query_events`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EventByIdParams {
+    /// Stable id of the event to look up (see [`crate::types::EconomicEvent::id`],
+    /// and the `id` field on results from `query_events`/`get_agenda`)
+    pub id: String,
+
+    /// Currency pair or single currency to narrow the search (optional)
+    #[serde(default)]
+    pub currencies: Option<String>,
+
+    /// Start date in YYYY-MM-DD format (optional)
+    #[serde(default)]
+    pub from_date: Option<String>,
+
+    /// End date in YYYY-MM-DD format (optional)
+    #[serde(default)]
+    pub to_date: Option<String>,
+
+    /// Minimum impact level (optional)
+    #[serde(default)]
+    pub min_impact: Option<String>,
+
+    /// IANA timezone to display the event's time in, e.g. "America/New_York"
+    /// (defaults to UTC)
+    #[serde(default)]
+    pub display_tz: Option<String>,
+}
+
+impl EventByIdParams {
+    /// Build the `QueryEventsParams` subset shared with `query_events`,
+    /// used to narrow the window searched for this id.
+    fn as_query_params(&self) -> QueryEventsParams {
+        QueryEventsParams {
+            currencies: self.currencies.clone(),
+            from_date: self.from_date.clone(),
+            to_date: self.to_date.clone(),
+            min_impact: self.min_impact.clone(),
+            format: None,
+            display_tz: self.display_tz.clone(),
+            exclude_holidays: None,
+            show_gaps: None,
+        }
+    }
+}
+
+/// Parameters for checking a recurrence rule for new or changed high-impact
+/// events.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct CheckScheduleParams {
+    /// systemd.time-style recurrence expression, e.g. "Mon..Fri 08:30",
+    /// "*:00", or "7..17/2:00" (see [`crate::schedule::ScheduleRule`])
+    pub schedule: String,
+
+    /// Minimum impact level: "low", "medium", "high" or 1-3 (defaults to
+    /// "high")
+    #[serde(default)]
+    pub min_impact: Option<String>,
+}
+
+impl CheckScheduleParams {
+    /// Parse min_impact string to impact level (1-3 or "low"/"medium"/"high").
+    pub fn parse_min_impact(&self) -> Option<crate::types::Impact> {
+        use crate::types::Impact;
+
+        self.min_impact.as_ref().and_then(|s| {
+            let s = s.trim().to_lowercase();
+            match s.as_str() {
+                "low" | "1" => Some(Impact::Low),
+                "medium" | "med" | "2" => Some(Impact::Medium),
+                "high" | "3" => Some(Impact::High),
+                _ => None,
+            }
+        })
+    }
+}
+
+/// An event whose `actual` value changed since the last `check_schedule` call.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ActualChange {
+    pub event: EventResult,
+
+    /// The `actual` value this event had the last time it was seen (absent
+    /// if it hadn't released yet).
+    pub previous_actual: Option<String>,
+}
+
+/// Result returned by the `check_schedule` MCP tool.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ScheduleCheckResult {
+    /// When this schedule rule will next trigger, in RFC 3339 format.
+    pub next_trigger: String,
+
+    /// Events meeting `min_impact` that haven't been seen by this watcher
+    /// before.
+    pub new_events: Vec<EventResult>,
+
+    /// Events seen before whose `actual` value has since changed.
+    pub changed_events: Vec<ActualChange>,
+}
+
+impl ScheduleCheckResult {
+    /// Build a result from a watcher's diffed changes, projecting each
+    /// event's time into `tz`.
+    pub fn from_changes(
+        changes: Vec<crate::schedule::WatchEvent>,
+        next_trigger: chrono::DateTime<Local>,
+        tz: Tz,
+    ) -> Self {
+        let mut new_events = Vec::new();
+        let mut changed_events = Vec::new();
+
+        for change in changes {
+            match change {
+                crate::schedule::WatchEvent::New(event) => {
+                    new_events.push(EventResult::from_event(event, tz));
+                }
+                crate::schedule::WatchEvent::ActualChanged {
+                    event,
+                    previous_actual,
+                } => {
+                    changed_events.push(ActualChange {
+                        event: EventResult::from_event(event, tz),
+                        previous_actual,
+                    });
+                }
+            }
+        }
+
+        Self {
+            next_trigger: next_trigger.to_rfc3339(),
+            new_events,
+            changed_events,
+        }
+    }
 }
 
 /// Parameters for getting events around a specific date
@@ -36,11 +195,143 @@ pub struct WeekAroundParams {
     /// Minimum impact level (optional)
     #[serde(default)]
     pub min_impact: Option<String>,
+
+    /// Output format: "json" (default) or "markdown"
+    #[serde(default)]
+    pub format: Option<String>,
+
+    /// IANA timezone to display event times/dates in, e.g. "America/New_York"
+    /// (defaults to UTC)
+    #[serde(default)]
+    pub display_tz: Option<String>,
+}
+
+/// Parameters shared by the zero-argument tools to select an output format.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FormatParams {
+    /// Output format: "json" (default) or "markdown"
+    #[serde(default)]
+    pub format: Option<String>,
+
+    /// IANA timezone to display event times/dates in, e.g. "America/New_York"
+    /// (defaults to UTC)
+    #[serde(default)]
+    pub display_tz: Option<String>,
+}
+
+/// Parse a user-supplied IANA timezone name, defaulting to `None` (UTC) for
+/// anything missing or unrecognized.
+fn parse_display_tz(display_tz: Option<&str>) -> Option<Tz> {
+    display_tz.and_then(|s| s.trim().parse::<Tz>().ok())
+}
+
+/// Resolve a single-date expression against `reference`: strict `YYYY-MM-DD`
+/// first, then a small relative-date grammar (case-insensitive,
+/// whitespace-tolerant): bare weekday names ("monday".."sunday") resolving to
+/// the next occurrence on/after `reference`; "today"/"tomorrow"/"yesterday";
+/// and "in N days"/"N days ago". Returns `None` for anything unrecognized,
+/// including week-span phrases (see [`resolve_week_span`]).
+fn resolve_date(raw: &str, reference: NaiveDate) -> Option<NaiveDate> {
+    let s = raw.trim().to_lowercase();
+
+    if let Ok(date) = NaiveDate::parse_from_str(&s, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    match s.as_str() {
+        "today" => return Some(reference),
+        "tomorrow" => return Some(reference + Duration::days(1)),
+        "yesterday" => return Some(reference - Duration::days(1)),
+        _ => {}
+    }
+
+    if let Some(weekday) = parse_weekday(&s) {
+        let mut date = reference;
+        for _ in 0..7 {
+            if date.weekday() == weekday {
+                return Some(date);
+            }
+            date += Duration::days(1);
+        }
+        return Some(date);
+    }
+
+    if let Some(n) = s
+        .strip_prefix("in ")
+        .and_then(|rest| rest.strip_suffix(" days"))
+        .and_then(|n| n.trim().parse::<i64>().ok())
+    {
+        return Duration::try_days(n).and_then(|d| reference.checked_add_signed(d));
+    }
+
+    if let Some(n) = s
+        .strip_suffix(" days ago")
+        .and_then(|n| n.trim().parse::<i64>().ok())
+    {
+        return Duration::try_days(n).and_then(|d| reference.checked_sub_signed(d));
+    }
+
+    None
+}
+
+/// Parse a full English weekday name, case-insensitive.
+fn parse_weekday(s: &str) -> Option<Weekday> {
+    match s {
+        "monday" => Some(Weekday::Mon),
+        "tuesday" => Some(Weekday::Tue),
+        "wednesday" => Some(Weekday::Wed),
+        "thursday" => Some(Weekday::Thu),
+        "friday" => Some(Weekday::Fri),
+        "saturday" => Some(Weekday::Sat),
+        "sunday" => Some(Weekday::Sun),
+        _ => None,
+    }
+}
+
+/// Resolve a week-span phrase ("this week"/"next week"/"last week",
+/// case-insensitive) into its Monday..Sunday bounds relative to `reference`.
+/// Returns `None` for anything else.
+fn resolve_week_span(raw: &str, reference: NaiveDate) -> Option<(NaiveDate, NaiveDate)> {
+    let s = raw.trim().to_lowercase();
+    let week_offset: i64 = match s.as_str() {
+        "this week" => 0,
+        "next week" => 1,
+        "last week" => -1,
+        _ => return None,
+    };
+
+    let this_monday = reference - Duration::days(reference.weekday().num_days_from_monday() as i64);
+    let monday = this_monday + Duration::weeks(week_offset);
+    Some((monday, monday + Duration::days(6)))
+}
+
+/// Output format requested for a tool's results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Json,
+    Markdown,
+}
+
+impl OutputFormat {
+    /// Parse a user-supplied format string, defaulting to JSON for anything
+    /// unrecognized so existing callers keep getting JSON.
+    pub fn parse(format: Option<&str>) -> Self {
+        match format.map(|s| s.trim().to_lowercase()) {
+            Some(s) if s == "markdown" || s == "md" => OutputFormat::Markdown,
+            _ => OutputFormat::Json,
+        }
+    }
 }
 
 /// Result event returned by the MCP tools
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct EventResult {
+    /// Stable id derived from the event's currency, name, and datetime (see
+    /// [`crate::types::EconomicEvent::id`]); pass this to `get_event_by_id`
+    /// to look up this event's details later.
+    pub id: String,
+
     /// Event name
     pub name: String,
 
@@ -64,6 +355,11 @@ pub struct EventResult {
     /// Previous period's value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub previous: Option<String>,
+
+    /// Human-readable beat/miss summary (e.g. "beat forecast by 50000
+    /// (+26.32%)"), if both `actual` and `forecast` are numeric
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub surprise: Option<String>,
 }
 
 impl QueryEventsParams {
@@ -80,18 +376,32 @@ impl QueryEventsParams {
             .unwrap_or_default()
     }
 
-    /// Parse from_date string to NaiveDate
+    /// Parse `from_date`, accepting strict `YYYY-MM-DD` or a relative
+    /// expression (see [`resolve_date`]/[`resolve_week_span`]). A week-span
+    /// phrase ("this week", "next week", "last week") resolves to its first
+    /// day here.
     pub fn parse_from_date(&self) -> Option<NaiveDate> {
+        let today = Local::now().date_naive();
         self.from_date
-            .as_ref()
-            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+            .as_deref()
+            .and_then(|s| resolve_week_span(s, today).map(|(start, _)| start).or_else(|| resolve_date(s, today)))
     }
 
-    /// Parse to_date string to NaiveDate
+    /// Parse `to_date`, accepting strict `YYYY-MM-DD` or a relative
+    /// expression. If `to_date` is unset but `from_date` is itself a
+    /// week-span phrase, its last day is used so both bounds can be filled
+    /// from that one phrase.
     pub fn parse_to_date(&self) -> Option<NaiveDate> {
-        self.to_date
-            .as_ref()
-            .and_then(|s| NaiveDate::parse_from_str(s, "%Y-%m-%d").ok())
+        let today = Local::now().date_naive();
+        if let Some(s) = self.to_date.as_deref() {
+            return resolve_week_span(s, today)
+                .map(|(_, end)| end)
+                .or_else(|| resolve_date(s, today));
+        }
+        self.from_date
+            .as_deref()
+            .and_then(|s| resolve_week_span(s, today))
+            .map(|(_, end)| end)
     }
 
     /// Parse min_impact string to impact level (1-3 or "low"/"medium"/"high")
@@ -108,25 +418,251 @@ impl QueryEventsParams {
             }
         })
     }
+
+    /// Parse the requested output format (defaults to JSON).
+    pub fn parse_format(&self) -> OutputFormat {
+        OutputFormat::parse(self.format.as_deref())
+    }
+
+    /// Parse the requested display timezone (defaults to UTC).
+    pub fn parse_display_tz(&self) -> Option<Tz> {
+        parse_display_tz(self.display_tz.as_deref())
+    }
+
+    /// Whether bank-holiday/market-closure events should be excluded
+    /// (defaults to `false`).
+    pub fn parse_exclude_holidays(&self) -> bool {
+        self.exclude_holidays.unwrap_or(false)
+    }
+
+    /// Whether the text agenda view should show empty days instead of
+    /// collapsing them out (defaults to `false`).
+    pub fn parse_show_gaps(&self) -> bool {
+        self.show_gaps.unwrap_or(false)
+    }
 }
 
-impl From<crate::types::EconomicEvent> for EventResult {
-    fn from(event: crate::types::EconomicEvent) -> Self {
+impl WeekAroundParams {
+    /// Parse the requested output format (defaults to JSON).
+    pub fn parse_format(&self) -> OutputFormat {
+        OutputFormat::parse(self.format.as_deref())
+    }
+
+    /// Parse the requested display timezone (defaults to UTC).
+    pub fn parse_display_tz(&self) -> Option<Tz> {
+        parse_display_tz(self.display_tz.as_deref())
+    }
+}
+
+impl FormatParams {
+    /// Parse the requested output format (defaults to JSON).
+    pub fn parse_format(&self) -> OutputFormat {
+        OutputFormat::parse(self.format.as_deref())
+    }
+
+    /// Parse the requested display timezone (defaults to UTC).
+    pub fn parse_display_tz(&self) -> Option<Tz> {
+        parse_display_tz(self.display_tz.as_deref())
+    }
+}
+
+impl EventResult {
+    /// Build a result, projecting the event's instant into `tz` for display.
+    pub fn from_event(event: crate::types::EconomicEvent, tz: Tz) -> Self {
+        let surprise = event.surprise().map(|s| s.to_string());
         Self {
+            id: event.id(),
             name: event.name,
-            currency: event.currency,
+            currency: event.currency.to_string(),
             impact: event.impact.to_string().to_lowercase(),
-            datetime: event.datetime.to_rfc3339(),
+            datetime: event.in_timezone(tz).to_rfc3339(),
             actual: event.actual,
             forecast: event.forecast,
             previous: event.previous,
+            surprise,
         }
     }
 }
 
+impl From<crate::types::EconomicEvent> for EventResult {
+    fn from(event: crate::types::EconomicEvent) -> Self {
+        Self::from_event(event, Tz::UTC)
+    }
+}
+
+/// A single day's worth of events, as returned by the agenda view.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct DayEvents {
+    /// Calendar date of this bucket (ISO 8601, e.g. "2025-06-02")
+    pub date: NaiveDate,
+
+    /// Full weekday name (e.g. "Monday")
+    pub weekday: String,
+
+    /// Events scheduled on this date, in chronological order
+    pub events: Vec<EventResult>,
+}
+
+impl DayEvents {
+    /// Convert a domain day bucket, projecting each event's time into `tz`.
+    pub fn from_day(day: crate::service::DayEvents, tz: Tz) -> Self {
+        Self {
+            weekday: day.date.format("%A").to_string(),
+            date: day.date,
+            events: day
+                .events
+                .into_iter()
+                .map(|e| EventResult::from_event(e, tz))
+                .collect(),
+        }
+    }
+}
+
+impl From<crate::service::DayEvents> for DayEvents {
+    fn from(day: crate::service::DayEvents) -> Self {
+        Self::from_day(day, Tz::UTC)
+    }
+}
+
+/// Render events as a Markdown agenda: events are grouped by day (bucketed
+/// and displayed in `tz`), and each day gets a heading followed by a
+/// `Time | Currency | Impact | Event | Actual | Forecast | Previous` table.
+pub fn render_markdown(events: Vec<crate::types::EconomicEvent>, tz: Tz) -> String {
+    let days = crate::service::group_by_day(events, Some(tz));
+    if days.is_empty() {
+        return "No events found.\n".to_string();
+    }
+
+    let mut out = String::new();
+    for day in &days {
+        out.push_str(&format!(
+            "## {}, {}\n\n",
+            day.date.format("%A"),
+            day.date.format("%Y-%m-%d")
+        ));
+        out.push_str("| Time | Currency | Impact | Event | Actual | Forecast | Previous |\n");
+        out.push_str("|---|---|---|---|---|---|---|\n");
+        for event in &day.events {
+            let stars = "\u{2605}".repeat(event.impact.stars() as usize);
+            out.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} |\n",
+                event.in_timezone(tz).format("%H:%M"),
+                event.currency,
+                stars,
+                event.name,
+                event.actual.as_deref().unwrap_or("-"),
+                event.forecast.as_deref().unwrap_or("-"),
+                event.previous.as_deref().unwrap_or("-"),
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Render events as a plain-text agenda for chat display: events are grouped
+/// by day (bucketed and displayed in `tz`), each day gets a `Weekday, Mon D`
+/// header followed by its events indented with time, currency, impact, and
+/// name. All-day/holiday events show `All Day` instead of a clock time.
+///
+/// If `range` is given, empty days within `[from, to]` are shown with a
+/// "(no events)" placeholder instead of being skipped; otherwise days with
+/// no events are collapsed out entirely. `EconomicEvent` only stores a
+/// single instant, so a closure is shown solely on the day it falls on
+/// rather than carried across every day of a multi-day holiday.
+pub fn render_agenda_text(
+    events: Vec<crate::types::EconomicEvent>,
+    tz: Tz,
+    range: Option<(NaiveDate, NaiveDate)>,
+) -> String {
+    let days = crate::service::group_by_day(events, Some(tz));
+    let days = match range {
+        Some((from, to)) => crate::service::fill_gaps(days, from, to),
+        None => days,
+    };
+
+    if days.is_empty() {
+        return "No events found.\n".to_string();
+    }
+
+    let mut out = String::new();
+    for day in &days {
+        out.push_str(&format!(
+            "{}, {}\n",
+            day.date.format("%A"),
+            day.date.format("%b %-d")
+        ));
+        if day.events.is_empty() {
+            out.push_str("  (no events)\n");
+        }
+        for event in &day.events {
+            let stars = "\u{2605}".repeat(event.impact.stars() as usize);
+            let time = if event.is_holiday {
+                "All Day".to_string()
+            } else {
+                event.in_timezone(tz).format("%H:%M").to_string()
+            };
+            out.push_str(&format!(
+                "  {time:<7} {:<4} {stars:<3} {}\n",
+                event.currency, event.name
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::types::{Currency, EconomicEvent, Impact};
+    use chrono::{TimeZone, Utc};
+
+    fn sample_event(day: u32, hour: u32) -> EconomicEvent {
+        EconomicEvent {
+            name: "Test Event".to_string(),
+            currency: Currency::Usd,
+            impact: Impact::High,
+            datetime: Utc.with_ymd_and_hms(2025, 6, day, hour, 0, 0).unwrap(),
+            actual: None,
+            forecast: None,
+            previous: None,
+            is_holiday: false,
+            affected_currencies: None,
+        }
+    }
+
+    #[test]
+    fn test_event_by_id_params_as_query_params_carries_filters_through() {
+        let params = EventByIdParams {
+            id: "some-id".to_string(),
+            currencies: Some("USD".to_string()),
+            from_date: Some("2025-06-02".to_string()),
+            to_date: Some("2025-06-03".to_string()),
+            min_impact: Some("high".to_string()),
+            display_tz: Some("America/New_York".to_string()),
+        };
+        let query_params = params.as_query_params();
+        assert_eq!(query_params.parse_currencies(), vec!["USD".to_string()]);
+        assert_eq!(
+            query_params.parse_from_date(),
+            Some(NaiveDate::from_ymd_opt(2025, 6, 2).unwrap())
+        );
+        assert_eq!(query_params.parse_min_impact(), Some(Impact::High));
+    }
+
+    #[test]
+    fn test_day_events_conversion_fills_weekday() {
+        let day = crate::service::DayEvents {
+            date: NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+            events: vec![sample_event(2, 8)],
+        };
+
+        let result: DayEvents = day.into();
+        assert_eq!(result.weekday, "Monday");
+        assert_eq!(result.events.len(), 1);
+        assert_eq!(result.events[0].name, "Test Event");
+    }
 
     #[test]
     fn test_parse_currencies() {
@@ -135,6 +671,10 @@ mod tests {
             from_date: None,
             to_date: None,
             min_impact: None,
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
         };
         assert_eq!(params.parse_currencies(), vec!["AUD", "CHF"]);
 
@@ -143,6 +683,10 @@ mod tests {
             from_date: None,
             to_date: None,
             min_impact: None,
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
         };
         assert_eq!(params.parse_currencies(), vec!["EUR", "GBP", "USD"]);
 
@@ -151,6 +695,10 @@ mod tests {
             from_date: None,
             to_date: None,
             min_impact: None,
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
         };
         assert!(params.parse_currencies().is_empty());
     }
@@ -162,6 +710,10 @@ mod tests {
             from_date: Some("2025-06-04".to_string()),
             to_date: Some("2025-06-10".to_string()),
             min_impact: None,
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
         };
 
         assert_eq!(
@@ -183,6 +735,10 @@ mod tests {
             from_date: None,
             to_date: None,
             min_impact: Some("high".to_string()),
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
         };
         assert_eq!(params.parse_min_impact(), Some(Impact::High));
 
@@ -191,7 +747,323 @@ mod tests {
             from_date: None,
             to_date: None,
             min_impact: Some("2".to_string()),
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
         };
         assert_eq!(params.parse_min_impact(), Some(Impact::Medium));
     }
+
+    #[test]
+    fn test_parse_format_defaults_to_json() {
+        assert_eq!(OutputFormat::parse(None), OutputFormat::Json);
+        assert_eq!(OutputFormat::parse(Some("bogus")), OutputFormat::Json);
+        assert_eq!(OutputFormat::parse(Some("JSON")), OutputFormat::Json);
+    }
+
+    #[test]
+    fn test_parse_format_markdown() {
+        assert_eq!(OutputFormat::parse(Some("markdown")), OutputFormat::Markdown);
+        assert_eq!(OutputFormat::parse(Some(" MD ")), OutputFormat::Markdown);
+    }
+
+    #[test]
+    fn test_render_markdown_groups_by_day_with_table() {
+        let events = vec![sample_event(2, 8), sample_event(3, 9)];
+        let markdown = render_markdown(events, Tz::UTC);
+        assert!(markdown.contains("## Monday, 2025-06-02"));
+        assert!(markdown.contains("## Tuesday, 2025-06-03"));
+        assert!(markdown.contains("| Time | Currency | Impact | Event | Actual | Forecast | Previous |"));
+        assert!(markdown.contains("★★★"));
+    }
+
+    #[test]
+    fn test_render_markdown_empty() {
+        assert_eq!(render_markdown(vec![], Tz::UTC), "No events found.\n");
+    }
+
+    #[test]
+    fn test_render_markdown_honors_display_tz() {
+        let events = vec![sample_event(2, 8)]; // 08:00 UTC
+        let markdown = render_markdown(events, chrono_tz::America::New_York);
+        assert!(markdown.contains("| 04:00 |")); // Eastern summer offset is -4
+    }
+
+    #[test]
+    fn test_render_agenda_text_groups_by_day() {
+        let events = vec![sample_event(2, 8), sample_event(3, 9)];
+        let agenda = render_agenda_text(events, Tz::UTC, None);
+        assert!(agenda.contains("Monday, Jun 2"));
+        assert!(agenda.contains("Tuesday, Jun 3"));
+        assert!(agenda.contains("08:00"));
+        assert!(agenda.contains("Test Event"));
+    }
+
+    #[test]
+    fn test_render_agenda_text_empty() {
+        assert_eq!(render_agenda_text(vec![], Tz::UTC, None), "No events found.\n");
+    }
+
+    #[test]
+    fn test_render_agenda_text_collapses_gaps_by_default() {
+        let events = vec![sample_event(2, 8), sample_event(4, 9)];
+        let agenda = render_agenda_text(events, Tz::UTC, None);
+        assert!(!agenda.contains("Jun 3"));
+    }
+
+    #[test]
+    fn test_render_agenda_text_shows_gaps_when_range_given() {
+        let events = vec![sample_event(2, 8), sample_event(4, 9)];
+        let agenda = render_agenda_text(
+            events,
+            Tz::UTC,
+            Some((
+                NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 6, 4).unwrap(),
+            )),
+        );
+        assert!(agenda.contains("Jun 3"));
+        assert!(agenda.contains("(no events)"));
+    }
+
+    #[test]
+    fn test_render_agenda_text_marks_holiday_events_all_day() {
+        let mut event = sample_event(2, 0);
+        event.is_holiday = true;
+        event.name = "Bank Holiday".to_string();
+        let agenda = render_agenda_text(vec![event], Tz::UTC, None);
+        assert!(agenda.contains("All Day"));
+    }
+
+    #[test]
+    fn test_event_result_includes_surprise_summary() {
+        let mut event = sample_event(2, 8);
+        event.actual = Some("240K".to_string());
+        event.forecast = Some("190K".to_string());
+        let result = EventResult::from_event(event, Tz::UTC);
+        assert_eq!(result.surprise.as_deref(), Some("beat forecast by 50000 (+26.32%)"));
+    }
+
+    #[test]
+    fn test_event_result_surprise_absent_without_actual() {
+        let result = EventResult::from_event(sample_event(2, 8), Tz::UTC);
+        assert_eq!(result.surprise, None);
+    }
+
+    #[test]
+    fn test_event_result_id_matches_source_event() {
+        let event = sample_event(2, 8);
+        let expected_id = event.id();
+        let result = EventResult::from_event(event, Tz::UTC);
+        assert_eq!(result.id, expected_id);
+    }
+
+    #[test]
+    fn test_check_schedule_params_parse_min_impact_defaults_to_none() {
+        let params = CheckScheduleParams {
+            schedule: "Mon..Fri 08:30".to_string(),
+            min_impact: None,
+        };
+        assert_eq!(params.parse_min_impact(), None);
+
+        let params = CheckScheduleParams {
+            min_impact: Some("high".to_string()),
+            ..params
+        };
+        assert_eq!(params.parse_min_impact(), Some(Impact::High));
+    }
+
+    #[test]
+    fn test_schedule_check_result_from_changes_splits_new_and_changed() {
+        let new_event = sample_event(2, 8);
+        let mut changed_event = sample_event(3, 9);
+        changed_event.actual = Some("240K".to_string());
+        let next_trigger = chrono::Local.with_ymd_and_hms(2025, 6, 4, 8, 30, 0).unwrap();
+
+        let result = ScheduleCheckResult::from_changes(
+            vec![
+                crate::schedule::WatchEvent::New(new_event),
+                crate::schedule::WatchEvent::ActualChanged {
+                    event: changed_event,
+                    previous_actual: None,
+                },
+            ],
+            next_trigger,
+            Tz::UTC,
+        );
+
+        assert_eq!(result.new_events.len(), 1);
+        assert_eq!(result.changed_events.len(), 1);
+        assert_eq!(result.changed_events[0].event.actual.as_deref(), Some("240K"));
+        assert_eq!(result.changed_events[0].previous_actual, None);
+        assert_eq!(result.next_trigger, next_trigger.to_rfc3339());
+    }
+
+    #[test]
+    fn test_resolve_date_strict_iso() {
+        let reference = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        assert_eq!(
+            resolve_date("2025-06-10", reference),
+            Some(NaiveDate::from_ymd_opt(2025, 6, 10).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_resolve_date_today_tomorrow_yesterday() {
+        // 2025-06-04 is a Wednesday.
+        let reference = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        assert_eq!(resolve_date("today", reference), Some(reference));
+        assert_eq!(resolve_date(" Today ", reference), Some(reference));
+        assert_eq!(
+            resolve_date("tomorrow", reference),
+            Some(NaiveDate::from_ymd_opt(2025, 6, 5).unwrap())
+        );
+        assert_eq!(
+            resolve_date("YESTERDAY", reference),
+            Some(NaiveDate::from_ymd_opt(2025, 6, 3).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_resolve_date_weekday_name_resolves_to_next_occurrence() {
+        // 2025-06-04 is a Wednesday.
+        let reference = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        assert_eq!(resolve_date("wednesday", reference), Some(reference)); // today itself
+        assert_eq!(
+            resolve_date("Friday", reference),
+            Some(NaiveDate::from_ymd_opt(2025, 6, 6).unwrap())
+        );
+        assert_eq!(
+            resolve_date("monday", reference),
+            Some(NaiveDate::from_ymd_opt(2025, 6, 9).unwrap())
+        ); // next Monday, not this week's
+    }
+
+    #[test]
+    fn test_resolve_date_relative_offsets() {
+        let reference = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        assert_eq!(
+            resolve_date("in 3 days", reference),
+            Some(NaiveDate::from_ymd_opt(2025, 6, 7).unwrap())
+        );
+        assert_eq!(
+            resolve_date("5 days ago", reference),
+            Some(NaiveDate::from_ymd_opt(2025, 5, 30).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_resolve_date_relative_offset_overflow_returns_none_instead_of_panicking() {
+        let reference = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        // Past chrono::Duration's internal millisecond bound (i64::MAX ms),
+        // not just NaiveDate's range - Duration::days(n) itself panics here.
+        assert_eq!(resolve_date("in 999999999999999 days", reference), None);
+        assert_eq!(resolve_date("999999999999999 days ago", reference), None);
+        assert_eq!(resolve_date(&format!("in {} days", i64::MAX), reference), None);
+        assert_eq!(resolve_date(&format!("{} days ago", i64::MAX), reference), None);
+    }
+
+    #[test]
+    fn test_resolve_date_unrecognized_returns_none() {
+        let reference = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        assert_eq!(resolve_date("not a date", reference), None);
+        assert_eq!(resolve_date("", reference), None);
+    }
+
+    #[test]
+    fn test_resolve_week_span() {
+        // 2025-06-04 is a Wednesday; that week's Monday is 2025-06-02.
+        let reference = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        assert_eq!(
+            resolve_week_span("this week", reference),
+            Some((
+                NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 6, 8).unwrap()
+            ))
+        );
+        assert_eq!(
+            resolve_week_span("Next Week", reference),
+            Some((
+                NaiveDate::from_ymd_opt(2025, 6, 9).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 6, 15).unwrap()
+            ))
+        );
+        assert_eq!(
+            resolve_week_span("last week", reference),
+            Some((
+                NaiveDate::from_ymd_opt(2025, 5, 26).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 6, 1).unwrap()
+            ))
+        );
+        assert_eq!(resolve_week_span("today", reference), None);
+    }
+
+    #[test]
+    fn test_query_events_params_fills_both_bounds_from_week_span() {
+        let params = QueryEventsParams {
+            currencies: None,
+            from_date: Some("next week".to_string()),
+            to_date: None,
+            min_impact: None,
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
+        };
+        let from = params.parse_from_date().unwrap();
+        let to = params.parse_to_date().unwrap();
+        assert_eq!(to - from, Duration::days(6));
+        assert_eq!(from.weekday(), Weekday::Mon);
+        assert_eq!(to.weekday(), Weekday::Sun);
+    }
+
+    #[test]
+    fn test_parse_display_tz() {
+        assert_eq!(parse_display_tz(Some("America/New_York")), Some(chrono_tz::America::New_York));
+        assert_eq!(parse_display_tz(Some("bogus/zone")), None);
+        assert_eq!(parse_display_tz(None), None);
+    }
+
+    #[test]
+    fn test_parse_exclude_holidays_defaults_to_false() {
+        let params = QueryEventsParams {
+            currencies: None,
+            from_date: None,
+            to_date: None,
+            min_impact: None,
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
+        };
+        assert!(!params.parse_exclude_holidays());
+
+        let params = QueryEventsParams {
+            exclude_holidays: Some(true),
+            ..params
+        };
+        assert!(params.parse_exclude_holidays());
+    }
+
+    #[test]
+    fn test_parse_show_gaps_defaults_to_false() {
+        let params = QueryEventsParams {
+            currencies: None,
+            from_date: None,
+            to_date: None,
+            min_impact: None,
+            format: None,
+            display_tz: None,
+            exclude_holidays: None,
+            show_gaps: None,
+        };
+        assert!(!params.parse_show_gaps());
+
+        let params = QueryEventsParams {
+            show_gaps: Some(true),
+            ..params
+        };
+        assert!(params.parse_show_gaps());
+    }
 }
@@ -0,0 +1,462 @@
+//! systemd.time-style recurrence expressions for a background high-impact
+//! event watcher.
+//!
+//! [`ScheduleRule`] parses expressions of the form `[weekdays] HH:MM`, where
+//! `weekdays` is optional (defaulting to every day) and each of the weekday,
+//! hour, and minute components accepts a comma list, a range (`a..b`), a
+//! stepped range (`a..b/n`), or `*` for "every value". [`HighImpactWatcher`]
+//! diffs a batch of already-fetched events against what it saw last time and
+//! reports only ones that are newly seen or whose `actual` value has
+//! changed, restricted to a minimum impact level.
+//!
+//! This server's transport is request/response, not a background task
+//! runner, so nothing here spawns its own polling loop. Instead, the
+//! `check_schedule` MCP tool (see [`crate::mcp::server`]) drives both pieces
+//! on demand: an external caller (e.g. a systemd timer or cron job) invokes
+//! it at the cadence it wants watched, `ScheduleRule::next_trigger` tells it
+//! when to call again, and each call's fetched events are run through a
+//! server-held [`HighImpactWatcher`] so repeat calls only surface what's new.
+
+use std::collections::{BTreeSet, HashMap};
+
+use chrono::{DateTime, Datelike, Duration, Local, Timelike, Weekday};
+use color_eyre::{Result, eyre::eyre};
+
+use crate::types::{EconomicEvent, Impact};
+
+/// A parsed systemd.time-style recurrence rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScheduleRule {
+    weekdays: BTreeSet<Weekday>,
+    hours: BTreeSet<u32>,
+    minutes: BTreeSet<u32>,
+}
+
+impl ScheduleRule {
+    /// Parse a `[weekdays] HH:MM` expression, e.g. `"Mon..Fri 08:30"`,
+    /// `"09:00,12:00"`, `"Mon,Wed,Fri 7..17/2:00"`, or `"* *:*"`.
+    ///
+    /// A comma list of whole times (`"09:00,12:00,15:30"`) adds each entry's
+    /// hour and minute to the rule's hour and minute sets, so the rule fires
+    /// at every combination of those hours and minutes.
+    pub fn parse(expr: &str) -> Result<Self> {
+        let expr = expr.trim();
+        let (weekday_part, time_part) = match expr.rsplit_once(' ') {
+            Some((w, t)) if t.contains(':') => (w.trim(), t.trim()),
+            _ => ("", expr),
+        };
+
+        let (hours, minutes) = if time_part.matches(':').count() > 1 {
+            let mut hours = BTreeSet::new();
+            let mut minutes = BTreeSet::new();
+            for time in time_part.split(',') {
+                let (hour_expr, minute_expr) = time
+                    .split_once(':')
+                    .ok_or_else(|| eyre!("expected HH:MM time in '{time}' of '{expr}'"))?;
+                hours.extend(parse_component(hour_expr, 23)?);
+                minutes.extend(parse_component(minute_expr, 59)?);
+            }
+            (hours, minutes)
+        } else {
+            let (hour_expr, minute_expr) = time_part
+                .split_once(':')
+                .ok_or_else(|| eyre!("expected HH:MM time component in '{expr}'"))?;
+            (parse_component(hour_expr, 23)?, parse_component(minute_expr, 59)?)
+        };
+
+        Ok(Self {
+            weekdays: parse_weekdays(weekday_part)?,
+            hours,
+            minutes,
+        })
+    }
+
+    /// Find the next instant strictly after `now` at which this rule fires,
+    /// by advancing minute-by-minute until every component matches.
+    /// Returns an error if the rule matches no point in time (an empty
+    /// component), or none is found within a week-and-a-day search window
+    /// (e.g. an hour value above 23).
+    pub fn next_trigger(&self, now: DateTime<Local>) -> Result<DateTime<Local>> {
+        if self.weekdays.is_empty() || self.hours.is_empty() || self.minutes.is_empty() {
+            return Err(eyre!("rule has an empty component and matches no time"));
+        }
+
+        let mut candidate = (now + Duration::minutes(1))
+            .with_second(0)
+            .and_then(|dt| dt.with_nanosecond(0))
+            .ok_or_else(|| eyre!("failed to truncate {now} to whole minutes"))?;
+        let limit = candidate + Duration::days(8);
+
+        while candidate < limit {
+            if self.weekdays.contains(&candidate.weekday())
+                && self.hours.contains(&candidate.hour())
+                && self.minutes.contains(&candidate.minute())
+            {
+                return Ok(candidate);
+            }
+            candidate += Duration::minutes(1);
+        }
+
+        Err(eyre!("no matching trigger time found for rule within 8 days of {now}"))
+    }
+}
+
+/// Parse a comma-separated list of single values, ranges (`a..b`), or
+/// stepped ranges (`a..b/n`), or `*` for every value in `0..=max`.
+fn parse_component(expr: &str, max: u32) -> Result<BTreeSet<u32>> {
+    let expr = expr.trim();
+    if expr == "*" {
+        return Ok((0..=max).collect());
+    }
+
+    let mut values = BTreeSet::new();
+    for part in expr.split(',') {
+        let part = part.trim();
+        if let Some((range, step)) = part.split_once('/') {
+            let (lo, hi) = parse_numeric_range(range)?;
+            let step: u32 = step
+                .trim()
+                .parse()
+                .map_err(|_| eyre!("invalid step in '{part}'"))?;
+            if step == 0 {
+                return Err(eyre!("step must be greater than zero in '{part}'"));
+            }
+            let mut v = lo;
+            while v <= hi {
+                values.insert(v);
+                v += step;
+            }
+        } else if part.contains("..") {
+            let (lo, hi) = parse_numeric_range(part)?;
+            values.extend(lo..=hi);
+        } else {
+            values.insert(
+                part.parse()
+                    .map_err(|_| eyre!("invalid numeric value '{part}'"))?,
+            );
+        }
+    }
+    Ok(values)
+}
+
+fn parse_numeric_range(expr: &str) -> Result<(u32, u32)> {
+    let (lo, hi) = expr
+        .split_once("..")
+        .ok_or_else(|| eyre!("expected a range like 'a..b' in '{expr}'"))?;
+    let lo: u32 = lo.trim().parse().map_err(|_| eyre!("invalid range start '{lo}'"))?;
+    let hi: u32 = hi.trim().parse().map_err(|_| eyre!("invalid range end '{hi}'"))?;
+    if lo > hi {
+        return Err(eyre!("range start {lo} is after end {hi} in '{expr}'"));
+    }
+    Ok((lo, hi))
+}
+
+/// Parse a comma-separated list of weekday names or weekday ranges
+/// (`Mon..Fri`), case-insensitive; an empty string or `*` means every day.
+fn parse_weekdays(expr: &str) -> Result<BTreeSet<Weekday>> {
+    if expr.is_empty() || expr == "*" {
+        return Ok(all_weekdays());
+    }
+
+    let mut weekdays = BTreeSet::new();
+    for part in expr.split(',') {
+        let part = part.trim();
+        if let Some((lo, hi)) = part.split_once("..") {
+            let lo = parse_weekday(lo.trim())?;
+            let hi = parse_weekday(hi.trim())?;
+            let mut day = lo;
+            loop {
+                weekdays.insert(day);
+                if day == hi {
+                    break;
+                }
+                day = day.succ();
+            }
+        } else {
+            weekdays.insert(parse_weekday(part)?);
+        }
+    }
+    Ok(weekdays)
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday> {
+    match s.to_lowercase().as_str() {
+        "mon" | "monday" => Ok(Weekday::Mon),
+        "tue" | "tuesday" => Ok(Weekday::Tue),
+        "wed" | "wednesday" => Ok(Weekday::Wed),
+        "thu" | "thursday" => Ok(Weekday::Thu),
+        "fri" | "friday" => Ok(Weekday::Fri),
+        "sat" | "saturday" => Ok(Weekday::Sat),
+        "sun" | "sunday" => Ok(Weekday::Sun),
+        _ => Err(eyre!("unknown weekday '{s}'")),
+    }
+}
+
+fn all_weekdays() -> BTreeSet<Weekday> {
+    [
+        Weekday::Mon,
+        Weekday::Tue,
+        Weekday::Wed,
+        Weekday::Thu,
+        Weekday::Fri,
+        Weekday::Sat,
+        Weekday::Sun,
+    ]
+    .into_iter()
+    .collect()
+}
+
+/// A newly-observed event or an `actual` value change surfaced by
+/// [`HighImpactWatcher::check`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum WatchEvent {
+    /// This event (by [`EconomicEvent::id`]) hasn't been seen before.
+    New(EconomicEvent),
+    /// This event was seen before with a different (or absent) `actual` value.
+    ActualChanged {
+        event: EconomicEvent,
+        previous_actual: Option<String>,
+    },
+}
+
+/// Tracks previously-seen high-impact events so repeated checks only report
+/// genuinely new information: brand-new events, or events whose `actual`
+/// value was just released/updated.
+#[derive(Debug, Default)]
+pub struct HighImpactWatcher {
+    last_actual: HashMap<String, Option<String>>,
+}
+
+impl HighImpactWatcher {
+    /// Create a watcher with no prior state; the first `check` reports
+    /// every event meeting `min_impact` as [`WatchEvent::New`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Diff `events` (already fetched, e.g. via
+    /// [`crate::service::CalendarService::query_events`]) against what this
+    /// watcher last saw, returning only events meeting `min_impact` that are
+    /// new or whose `actual` value has changed since the last call.
+    pub fn check(&mut self, events: Vec<EconomicEvent>, min_impact: Impact) -> Vec<WatchEvent> {
+        let mut changes = Vec::new();
+        for event in events.into_iter().filter(|e| e.meets_impact(min_impact)) {
+            let id = event.id();
+            match self.last_actual.get(&id) {
+                None => {
+                    self.last_actual.insert(id, event.actual.clone());
+                    changes.push(WatchEvent::New(event));
+                }
+                Some(previous) if previous != &event.actual => {
+                    let previous_actual = previous.clone();
+                    self.last_actual.insert(id, event.actual.clone());
+                    changes.push(WatchEvent::ActualChanged {
+                        event,
+                        previous_actual,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        changes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_parse_wildcard_everything() {
+        let rule = ScheduleRule::parse("*:*").unwrap();
+        assert_eq!(rule.weekdays, all_weekdays());
+        assert_eq!(rule.hours.len(), 24);
+        assert_eq!(rule.minutes.len(), 60);
+    }
+
+    #[test]
+    fn test_parse_without_weekday_defaults_to_every_day() {
+        let rule = ScheduleRule::parse("08:30").unwrap();
+        assert_eq!(rule.weekdays, all_weekdays());
+        assert_eq!(rule.hours, BTreeSet::from([8]));
+        assert_eq!(rule.minutes, BTreeSet::from([30]));
+    }
+
+    #[test]
+    fn test_parse_weekday_range() {
+        let rule = ScheduleRule::parse("Mon..Fri 08:30").unwrap();
+        assert_eq!(
+            rule.weekdays,
+            BTreeSet::from([
+                Weekday::Mon,
+                Weekday::Tue,
+                Weekday::Wed,
+                Weekday::Thu,
+                Weekday::Fri
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_weekday_comma_list() {
+        let rule = ScheduleRule::parse("Mon,Wed,Fri 09:00").unwrap();
+        assert_eq!(
+            rule.weekdays,
+            BTreeSet::from([Weekday::Mon, Weekday::Wed, Weekday::Fri])
+        );
+    }
+
+    #[test]
+    fn test_parse_comma_list_of_times() {
+        let rule = ScheduleRule::parse("09:00,12:00,15:30").unwrap();
+        assert_eq!(rule.hours, BTreeSet::from([9, 12, 15]));
+        assert_eq!(rule.minutes, BTreeSet::from([0, 30]));
+    }
+
+    #[test]
+    fn test_parse_step_expansion() {
+        let rule = ScheduleRule::parse("7..17/2:00").unwrap();
+        assert_eq!(rule.hours, BTreeSet::from([7, 9, 11, 13, 15, 17]));
+    }
+
+    #[test]
+    fn test_parse_rejects_zero_step() {
+        assert!(ScheduleRule::parse("7..17/0:00").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_backwards_range() {
+        assert!(ScheduleRule::parse("17..7:00").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_missing_time() {
+        assert!(ScheduleRule::parse("Mon..Fri").is_err());
+    }
+
+    #[test]
+    fn test_parse_rejects_unknown_weekday() {
+        assert!(ScheduleRule::parse("Funday 08:00").is_err());
+    }
+
+    #[test]
+    fn test_next_trigger_later_same_day() {
+        let rule = ScheduleRule::parse("08:30").unwrap();
+        // 2025-06-04 is a Wednesday.
+        let now = Local.with_ymd_and_hms(2025, 6, 4, 7, 0, 0).unwrap();
+        let next = rule.next_trigger(now).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2025, 6, 4, 8, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_next_trigger_wraps_into_next_day() {
+        let rule = ScheduleRule::parse("08:30").unwrap();
+        let now = Local.with_ymd_and_hms(2025, 6, 4, 9, 0, 0).unwrap();
+        let next = rule.next_trigger(now).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2025, 6, 5, 8, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_next_trigger_wraps_into_next_week() {
+        // Rule only fires Mondays; starting on a Wednesday should land on
+        // the following Monday, not this week's.
+        let rule = ScheduleRule::parse("Mon 08:30").unwrap();
+        let now = Local.with_ymd_and_hms(2025, 6, 4, 9, 0, 0).unwrap(); // Wednesday
+        let next = rule.next_trigger(now).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2025, 6, 9, 8, 30, 0).unwrap()); // next Monday
+        assert_eq!(next.weekday(), Weekday::Mon);
+    }
+
+    #[test]
+    fn test_next_trigger_fires_exactly_at_boundary_uses_next_minute() {
+        let rule = ScheduleRule::parse("08:30").unwrap();
+        let now = Local.with_ymd_and_hms(2025, 6, 4, 8, 30, 0).unwrap();
+        let next = rule.next_trigger(now).unwrap();
+        // Already at 08:30; the next trigger must be strictly after `now`,
+        // so it rolls to tomorrow's 08:30, not the same instant.
+        assert_eq!(next, Local.with_ymd_and_hms(2025, 6, 5, 8, 30, 0).unwrap());
+    }
+
+    #[test]
+    fn test_next_trigger_errors_on_no_matching_component() {
+        // Built directly: `parse` never yields an empty component, since a
+        // zero step is rejected and any other step includes the range start.
+        let rule = ScheduleRule {
+            weekdays: all_weekdays(),
+            hours: BTreeSet::new(),
+            minutes: BTreeSet::from([0]),
+        };
+        let now = Local.with_ymd_and_hms(2025, 6, 4, 9, 0, 0).unwrap();
+        assert!(rule.next_trigger(now).is_err());
+    }
+
+    fn sample_event(name: &str, actual: Option<&str>) -> EconomicEvent {
+        use chrono::{TimeZone, Utc};
+        EconomicEvent {
+            name: name.to_string(),
+            currency: crate::types::Currency::Usd,
+            impact: Impact::High,
+            datetime: Utc.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap(),
+            actual: actual.map(str::to_string),
+            forecast: Some("190K".to_string()),
+            previous: Some("175K".to_string()),
+            is_holiday: false,
+            affected_currencies: None,
+        }
+    }
+
+    #[test]
+    fn test_watcher_reports_new_events_once() {
+        let mut watcher = HighImpactWatcher::new();
+
+        let first = watcher.check(vec![sample_event("Non-Farm Payrolls", None)], Impact::High);
+        assert_eq!(first.len(), 1);
+        assert!(matches!(first[0], WatchEvent::New(_)));
+
+        let second = watcher.check(vec![sample_event("Non-Farm Payrolls", None)], Impact::High);
+        assert!(second.is_empty());
+    }
+
+    #[test]
+    fn test_watcher_reports_actual_value_changes() {
+        let mut watcher = HighImpactWatcher::new();
+
+        watcher.check(vec![sample_event("Non-Farm Payrolls", None)], Impact::High);
+
+        let changes = watcher.check(
+            vec![sample_event("Non-Farm Payrolls", Some("240K"))],
+            Impact::High,
+        );
+
+        assert_eq!(changes.len(), 1);
+        match &changes[0] {
+            WatchEvent::ActualChanged {
+                event,
+                previous_actual,
+            } => {
+                assert_eq!(event.actual.as_deref(), Some("240K"));
+                assert_eq!(previous_actual, &None);
+            }
+            other => panic!("expected ActualChanged, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_watcher_ignores_events_below_min_impact() {
+        let mut watcher = HighImpactWatcher::new();
+        let mut low_impact_event = sample_event("Retail Sales", None);
+        low_impact_event.impact = Impact::Low;
+
+        let changes = watcher.check(vec![low_impact_event], Impact::High);
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_watch_event_debug_includes_event_name() {
+        let event = sample_event("Test Event", None);
+        let watch = WatchEvent::New(event);
+        assert!(format!("{watch:?}").contains("Test Event"));
+    }
+}
@@ -0,0 +1,8 @@
+mod browser;
+mod fetcher;
+mod http_client;
+mod parser;
+
+pub use fetcher::CalendarFetcher;
+pub use http_client::HttpCalendarFetcher;
+pub use parser::{CalendarParser, DEFAULT_SOURCE_TZ};
@@ -4,12 +4,15 @@ use headless_chrome::{Browser, LaunchOptions};
 use std::time::Duration;
 use tracing::{debug, info};
 
-/// Fetches the raw HTML content from Forex Factory calendar page.
-pub struct CalendarFetcher {
+/// Fetches the raw HTML content from Forex Factory calendar page by driving
+/// a real headless Chrome instance, for cases where [`super::HttpCalendarFetcher`]
+/// can't get past Cloudflare at all. Not currently wired into [`super::CalendarFetcher`]
+/// (its methods are synchronous), but kept as a reference implementation.
+pub struct BrowserCalendarFetcher {
     browser: Browser,
 }
 
-impl CalendarFetcher {
+impl BrowserCalendarFetcher {
     /// Create a new fetcher with a headless Chrome browser.
     pub fn new() -> Result<Self> {
         info!("Launching headless Chrome browser...");
@@ -0,0 +1,61 @@
+//! The [`CalendarFetcher`] trait abstracts *how* calendar HTML is obtained
+//! (plain HTTP, a headless browser, a challenge-solving proxy, or a mock in
+//! tests) away from the parser and service layers that consume it.
+
+use async_trait::async_trait;
+use chrono::{Datelike, NaiveDate};
+use chrono_tz::Tz;
+use color_eyre::Result;
+
+/// Fetches raw Forex Factory calendar HTML for a given week/date.
+///
+/// Implementations decide how to handle transient failures and Cloudflare's
+/// bot-check page; callers only see a final `Result<String>`.
+#[async_trait]
+pub trait CalendarFetcher: Send + Sync {
+    /// Fetch calendar HTML for a specific week, e.g. "jun4.2025".
+    async fn fetch_week(&self, week: &str) -> Result<String>;
+
+    /// Fetch calendar HTML for the week containing `date`.
+    async fn fetch_date(&self, date: NaiveDate) -> Result<String> {
+        self.fetch_week(&format_week_param(date)).await
+    }
+
+    /// Fetch calendar HTML for today.
+    async fn fetch_today(&self) -> Result<String>;
+
+    /// Fetch calendar HTML for the current week.
+    async fn fetch_this_week(&self) -> Result<String>;
+
+    /// Timezone the fetched HTML's wall-clock times should be parsed as,
+    /// e.g. pinned via a site cookie (see `HttpCalendarFetcher::with_source_tz`).
+    /// Defaults to [`super::DEFAULT_SOURCE_TZ`] for fetchers that don't pin one.
+    fn source_tz(&self) -> Tz {
+        super::DEFAULT_SOURCE_TZ
+    }
+}
+
+/// Format a date into Forex Factory's week parameter format, e.g. "jun4.2025".
+pub(crate) fn format_week_param(date: NaiveDate) -> String {
+    let month = date.format("%b").to_string().to_lowercase();
+    let day = date.day();
+    let year = date.year();
+    format!("{month}{day}.{year}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_week_param() {
+        let date = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
+        assert_eq!(format_week_param(date), "jun4.2025");
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        assert_eq!(format_week_param(date), "jan15.2025");
+
+        let date = NaiveDate::from_ymd_opt(2025, 12, 25).unwrap();
+        assert_eq!(format_week_param(date), "dec25.2025");
+    }
+}
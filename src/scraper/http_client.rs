@@ -1,19 +1,97 @@
-use chrono::{Datelike, NaiveDate};
+use async_trait::async_trait;
+use chrono_tz::Tz;
 use color_eyre::{Result, eyre::eyre};
-use reqwest::Client;
-use reqwest::header::{ACCEPT, ACCEPT_LANGUAGE, HeaderMap, HeaderValue, USER_AGENT};
-use std::time::Duration;
-use tracing::{debug, info};
+use rand::Rng;
+use reqwest::cookie::Jar;
+use reqwest::header::{
+    ACCEPT, ACCEPT_LANGUAGE, ETAG, HeaderMap, HeaderValue, IF_MODIFIED_SINCE, IF_NONE_MATCH,
+    LAST_MODIFIED, USER_AGENT,
+};
+use reqwest::{Client, StatusCode};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use super::{CalendarFetcher, DEFAULT_SOURCE_TZ};
+
+/// Forex Factory localizes the times it renders to whatever timezone
+/// preference is stored in the `fftimezone` cookie; without it, the site
+/// falls back to geolocating the request. [`HttpCalendarFetcher`] pins this
+/// cookie to a chosen [`Tz`] (UTC by default, via [`DEFAULT_SOURCE_TZ`]) so
+/// the timezone passed to [`super::CalendarParser::parse`] is guaranteed to
+/// match what the scraped page actually rendered, regardless of where the
+/// server runs.
+fn timezone_cookie(tz: Tz) -> String {
+    // IANA zone names only ever contain letters, digits, '_', '-', '/', and
+    // '+' (e.g. "America/New_York", "Etc/GMT+5"); percent-encode just the
+    // two characters that aren't valid in a cookie value.
+    let encoded = tz.name().replace('/', "%2F").replace('+', "%2B");
+    format!("fftimezone={encoded}; Max-Age=31536000")
+}
+
+/// Default time a fetched page is considered fresh before we even attempt a
+/// conditional revalidation request.
+const DEFAULT_CACHE_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// Default number of attempts (including the first) made against a URL
+/// before a transient failure or Cloudflare challenge is given up on.
+const DEFAULT_MAX_RETRIES: u32 = 3;
+
+/// Base delay for the exponential backoff between retries; doubles each
+/// attempt and is randomized by up to [`MAX_JITTER_MS`] to avoid thundering
+/// herds of retries all landing at once.
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_JITTER_MS: u64 = 250;
+
+/// A previously fetched calendar page, kept so we can revalidate cheaply or
+/// serve it straight from memory within the TTL.
+struct CacheEntry {
+    html: String,
+    etag: Option<String>,
+    last_modified: Option<String>,
+    fetched_at: Instant,
+}
+
+/// Outcome of a single fetch attempt, used by the retry loop to decide
+/// whether to give up, retry directly, or fall back to the solver proxy.
+enum Attempt {
+    Success(String),
+    /// Cloudflare served its bot-check page instead of the calendar.
+    ChallengeDetected,
+    /// A network error or non-2xx status; worth retrying.
+    Transient(color_eyre::Report),
+}
 
 /// Fetches the raw HTML content from Forex Factory calendar page using HTTP.
 pub struct HttpCalendarFetcher {
     client: Client,
+    cache: Mutex<HashMap<String, CacheEntry>>,
+    ttl: Duration,
+    max_retries: u32,
+    /// When set, a URL that trips Cloudflare's challenge is re-dispatched as
+    /// `{solver_url}?url={target}` instead of failing outright — pointed at
+    /// an external browser-emulation proxy that can clear the challenge.
+    solver_url: Option<String>,
+    /// The timezone pinned via the `fftimezone` cookie; scraped wall-clock
+    /// times should be parsed with this as the source timezone.
+    source_tz: Tz,
 }
 
 impl HttpCalendarFetcher {
-    /// Create a new fetcher with a configured HTTP client.
+    /// Create a new fetcher with a configured HTTP client, pinned to
+    /// [`DEFAULT_SOURCE_TZ`] (UTC).
     pub fn new() -> Result<Self> {
-        info!("Creating HTTP client for Forex Factory...");
+        Self::with_source_tz(DEFAULT_SOURCE_TZ)
+    }
+
+    /// Create a fetcher pinned to `tz`: the site is asked to render times in
+    /// `tz` via the `fftimezone` cookie, so callers can parse the scraped
+    /// HTML with `tz` (see [`CalendarFetcher::source_tz`]) instead of
+    /// assuming UTC.
+    pub fn with_source_tz(tz: Tz) -> Result<Self> {
+        info!("Creating HTTP client for Forex Factory (source_tz: {tz})...");
 
         let mut headers = HeaderMap::new();
 
@@ -45,56 +123,171 @@ impl HttpCalendarFetcher {
         headers.insert("Sec-Fetch-User", HeaderValue::from_static("?1"));
         headers.insert("Upgrade-Insecure-Requests", HeaderValue::from_static("1"));
 
+        let site_url: reqwest::Url = "https://www.forexfactory.com"
+            .parse()
+            .map_err(|e| eyre!("Invalid site URL: {e}"))?;
+        let jar = Jar::default();
+        jar.add_cookie_str(&timezone_cookie(tz), &site_url);
+
         let client = Client::builder()
             .default_headers(headers)
-            .cookie_store(true)
+            .cookie_provider(Arc::new(jar))
             .timeout(Duration::from_secs(30))
             .build()
             .map_err(|e| eyre!("Failed to build HTTP client: {e}"))?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            cache: Mutex::new(HashMap::new()),
+            ttl: DEFAULT_CACHE_TTL,
+            max_retries: DEFAULT_MAX_RETRIES,
+            solver_url: None,
+            source_tz: tz,
+        })
     }
 
-    /// Fetch calendar HTML for a specific week.
-    pub async fn fetch_week(&self, week: &str) -> Result<String> {
-        let url = format!("https://www.forexfactory.com/calendar?week={week}");
-        self.fetch_url(&url).await
+    /// Override how long a fetched page is served from cache before a
+    /// conditional revalidation request is attempted (default: 5 minutes).
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = ttl;
+        self
     }
 
-    /// Fetch calendar HTML for a date.
-    pub async fn fetch_date(&self, date: NaiveDate) -> Result<String> {
-        let week = format_week_param(date);
-        self.fetch_week(&week).await
+    /// Override how many attempts (including the first) are made against a
+    /// URL before giving up (default: 3).
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
     }
 
-    /// Fetch calendar HTML for today.
-    pub async fn fetch_today(&self) -> Result<String> {
-        self.fetch_url("https://www.forexfactory.com/calendar?day=today")
-            .await
+    /// Configure a solver proxy endpoint: when Cloudflare's challenge page is
+    /// detected, the target URL is re-dispatched as `{solver_url}?url={target}`
+    /// instead of failing outright. The proxy is expected to run the page
+    /// through a real browser and return the resulting HTML.
+    pub fn with_solver_url(mut self, solver_url: impl Into<String>) -> Self {
+        self.solver_url = Some(solver_url.into());
+        self
     }
 
-    /// Fetch calendar HTML for this week.
-    pub async fn fetch_this_week(&self) -> Result<String> {
-        self.fetch_url("https://www.forexfactory.com/calendar?week=this")
-            .await
+    /// Drop all cached pages, forcing the next fetch of each URL to hit the
+    /// network.
+    #[allow(dead_code)]
+    pub async fn clear_cache(&self) {
+        self.cache.lock().await.clear();
     }
 
-    /// Fetch the raw HTML from a URL.
+    /// Fetch the raw HTML from a URL, serving it from cache within the TTL,
+    /// and otherwise retrying transient failures and Cloudflare challenges
+    /// with exponential backoff (falling back to the solver proxy, if
+    /// configured, when a challenge is detected).
     async fn fetch_url(&self, url: &str) -> Result<String> {
+        {
+            let cache = self.cache.lock().await;
+            if let Some(entry) = cache.get(url)
+                && entry.fetched_at.elapsed() < self.ttl
+            {
+                debug!("Serving {url} from cache (within {:?} TTL)", self.ttl);
+                return Ok(entry.html.clone());
+            }
+        }
+
+        self.fetch_with_retry(url).await
+    }
+
+    /// Retry loop around [`Self::fetch_once`]: transient failures and
+    /// Cloudflare challenges are retried with jittered exponential backoff
+    /// (capped at `max_retries` attempts); a challenge additionally tries the
+    /// solver proxy, if one is configured. Any other error (e.g. the page
+    /// structure no longer matches what we expect) is not retried.
+    async fn fetch_with_retry(&self, url: &str) -> Result<String> {
+        let attempts = self.max_retries.max(1);
+        let mut last_err = None;
+
+        for attempt in 0..attempts {
+            if attempt > 0 {
+                let backoff = BASE_BACKOFF * 2u32.pow(attempt - 1);
+                let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..MAX_JITTER_MS));
+                debug!(
+                    "Retrying {url} (attempt {}/{attempts}) after {:?}",
+                    attempt + 1,
+                    backoff + jitter
+                );
+                tokio::time::sleep(backoff + jitter).await;
+            }
+
+            match self.fetch_once(url).await? {
+                Attempt::Success(html) => return Ok(html),
+                Attempt::Transient(e) => {
+                    warn!("Transient failure fetching {url}: {e}");
+                    last_err = Some(e);
+                }
+                Attempt::ChallengeDetected => {
+                    if let Some(solver_url) = self.solver_url.clone() {
+                        match self.fetch_via_solver(url, &solver_url).await {
+                            Ok(html) => return Ok(html),
+                            Err(e) => last_err = Some(e),
+                        }
+                    } else {
+                        last_err = Some(eyre!(
+                            "Cloudflare challenge detected for {url} and no solver_url is configured"
+                        ));
+                    }
+                }
+            }
+        }
+
+        Err(last_err
+            .unwrap_or_else(|| eyre!("Failed to fetch {url} after {attempts} attempts")))
+    }
+
+    /// Make a single attempt at `url`, classifying the result so the caller
+    /// can decide whether it's worth retrying. Errors returned directly
+    /// (rather than wrapped in `Attempt::Transient`) are treated as fatal.
+    async fn fetch_once(&self, url: &str) -> Result<Attempt> {
+        let validators = {
+            let cache = self.cache.lock().await;
+            cache
+                .get(url)
+                .map(|entry| (entry.etag.clone(), entry.last_modified.clone()))
+        };
+
         info!("Fetching calendar from: {url}");
 
-        let response = self
-            .client
-            .get(url)
-            .send()
-            .await
-            .map_err(|e| eyre!("Failed to fetch {url}: {e}"))?;
+        let mut request = self.client.get(url);
+        if let Some((etag, last_modified)) = &validators {
+            if let Some(etag) = etag {
+                request = request.header(IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = last_modified {
+                request = request.header(IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = match request.send().await {
+            Ok(response) => response,
+            Err(e) => return Ok(Attempt::Transient(eyre!("Failed to fetch {url}: {e}"))),
+        };
+
+        if response.status() == StatusCode::NOT_MODIFIED {
+            let mut cache = self.cache.lock().await;
+            let Some(entry) = cache.get_mut(url) else {
+                return Err(eyre!(
+                    "Received 304 Not Modified for {url} but no cached body is available"
+                ));
+            };
+            debug!("{url} not modified, reusing cached body");
+            entry.fetched_at = Instant::now();
+            return Ok(Attempt::Success(entry.html.clone()));
+        }
 
         let status = response.status();
         if !status.is_success() {
-            return Err(eyre!("HTTP error {status} for {url}"));
+            return Ok(Attempt::Transient(eyre!("HTTP error {status} for {url}")));
         }
 
+        let etag = header_str(&response, ETAG);
+        let last_modified = header_str(&response, LAST_MODIFIED);
+
         let html = response
             .text()
             .await
@@ -102,11 +295,8 @@ impl HttpCalendarFetcher {
 
         debug!("Successfully fetched {} bytes of HTML", html.len());
 
-        // Check if we hit Cloudflare challenge
-        if html.contains("Just a moment...") || html.contains("Verifying you are human") {
-            return Err(eyre!(
-                "Cloudflare challenge detected. The site requires browser verification."
-            ));
+        if is_cloudflare_challenge(&html) {
+            return Ok(Attempt::ChallengeDetected);
         }
 
         // Check if we got the calendar table
@@ -117,31 +307,192 @@ impl HttpCalendarFetcher {
             ));
         }
 
+        self.cache.lock().await.insert(
+            url.to_string(),
+            CacheEntry {
+                html: html.clone(),
+                etag,
+                last_modified,
+                fetched_at: Instant::now(),
+            },
+        );
+
+        Ok(Attempt::Success(html))
+    }
+
+    /// Re-dispatch `target_url` through a solver proxy expected to run it
+    /// through a real browser and hand back the resulting HTML.
+    async fn fetch_via_solver(&self, target_url: &str, solver_url: &str) -> Result<String> {
+        info!("Cloudflare challenge detected for {target_url}; retrying via solver proxy");
+
+        let response = self
+            .client
+            .get(solver_url)
+            .query(&[("url", target_url)])
+            .send()
+            .await
+            .map_err(|e| eyre!("Solver proxy request failed: {e}"))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(eyre!("Solver proxy returned HTTP {status} for {target_url}"));
+        }
+
+        let html = response
+            .text()
+            .await
+            .map_err(|e| eyre!("Failed to read solver proxy response: {e}"))?;
+
+        if is_cloudflare_challenge(&html) {
+            return Err(eyre!(
+                "Solver proxy also returned a Cloudflare challenge for {target_url}"
+            ));
+        }
+
+        self.cache.lock().await.insert(
+            target_url.to_string(),
+            CacheEntry {
+                html: html.clone(),
+                etag: None,
+                last_modified: None,
+                fetched_at: Instant::now(),
+            },
+        );
+
         Ok(html)
     }
 }
 
-/// Format a date into Forex Factory's week parameter format.
-fn format_week_param(date: NaiveDate) -> String {
-    let month = date.format("%b").to_string().to_lowercase();
-    let day = date.day();
-    let year = date.year();
-    format!("{month}{day}.{year}")
+#[async_trait]
+impl CalendarFetcher for HttpCalendarFetcher {
+    async fn fetch_week(&self, week: &str) -> Result<String> {
+        let url = format!("https://www.forexfactory.com/calendar?week={week}");
+        self.fetch_url(&url).await
+    }
+
+    async fn fetch_today(&self) -> Result<String> {
+        self.fetch_url("https://www.forexfactory.com/calendar?day=today")
+            .await
+    }
+
+    async fn fetch_this_week(&self) -> Result<String> {
+        self.fetch_url("https://www.forexfactory.com/calendar?week=this")
+            .await
+    }
+
+    fn source_tz(&self) -> Tz {
+        self.source_tz
+    }
+}
+
+/// Whether `html` is Cloudflare's "Just a moment..." / "Verifying you are
+/// human" interstitial rather than the actual calendar page.
+fn is_cloudflare_challenge(html: &str) -> bool {
+    html.contains("Just a moment...") || html.contains("Verifying you are human")
+}
+
+/// Read a response header as an owned string, if present and valid UTF-8.
+fn header_str(response: &reqwest::Response, name: reqwest::header::HeaderName) -> Option<String> {
+    response
+        .headers()
+        .get(name)
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_format_week_param() {
-        let date = NaiveDate::from_ymd_opt(2025, 6, 4).unwrap();
-        assert_eq!(format_week_param(date), "jun4.2025");
-    }
-
     #[test]
     fn test_client_creation() {
         let fetcher = HttpCalendarFetcher::new();
         assert!(fetcher.is_ok());
     }
+
+    #[test]
+    fn test_with_ttl_overrides_default() {
+        let fetcher = HttpCalendarFetcher::new()
+            .unwrap()
+            .with_ttl(Duration::from_secs(42));
+        assert_eq!(fetcher.ttl, Duration::from_secs(42));
+    }
+
+    #[test]
+    fn test_with_max_retries_overrides_default() {
+        let fetcher = HttpCalendarFetcher::new().unwrap().with_max_retries(5);
+        assert_eq!(fetcher.max_retries, 5);
+    }
+
+    #[test]
+    fn test_with_solver_url_sets_fallback() {
+        let fetcher = HttpCalendarFetcher::new()
+            .unwrap()
+            .with_solver_url("https://solver.example.com/render");
+        assert_eq!(
+            fetcher.solver_url.as_deref(),
+            Some("https://solver.example.com/render")
+        );
+    }
+
+    #[test]
+    fn test_timezone_cookie_applies_to_forexfactory_domain() {
+        use reqwest::cookie::CookieStore;
+
+        let jar = Jar::default();
+        let url: reqwest::Url = "https://www.forexfactory.com".parse().unwrap();
+        jar.add_cookie_str(&timezone_cookie(DEFAULT_SOURCE_TZ), &url);
+
+        let header = jar
+            .cookies(&url)
+            .expect("cookie should be set for forexfactory.com");
+        assert!(header.to_str().unwrap().contains("fftimezone"));
+    }
+
+    #[test]
+    fn test_timezone_cookie_percent_encodes_zone_name() {
+        assert_eq!(
+            timezone_cookie(chrono_tz::America::New_York),
+            "fftimezone=America%2FNew_York; Max-Age=31536000"
+        );
+    }
+
+    #[test]
+    fn test_with_source_tz_is_reported_by_the_fetcher() {
+        let fetcher = HttpCalendarFetcher::with_source_tz(chrono_tz::Asia::Tokyo).unwrap();
+        assert_eq!(fetcher.source_tz(), chrono_tz::Asia::Tokyo);
+    }
+
+    #[test]
+    fn test_new_defaults_to_default_source_tz() {
+        let fetcher = HttpCalendarFetcher::new().unwrap();
+        assert_eq!(fetcher.source_tz(), DEFAULT_SOURCE_TZ);
+    }
+
+    #[test]
+    fn test_is_cloudflare_challenge_detects_known_markers() {
+        assert!(is_cloudflare_challenge("<html>Just a moment...</html>"));
+        assert!(is_cloudflare_challenge("Verifying you are human"));
+        assert!(!is_cloudflare_challenge(
+            "<table class=\"calendar__table\"></table>"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_clear_cache_empties_cache() {
+        let fetcher = HttpCalendarFetcher::new().unwrap();
+        fetcher.cache.lock().await.insert(
+            "https://example.com".to_string(),
+            CacheEntry {
+                html: "<html></html>".to_string(),
+                etag: None,
+                last_modified: None,
+                fetched_at: Instant::now(),
+            },
+        );
+        assert_eq!(fetcher.cache.lock().await.len(), 1);
+
+        fetcher.clear_cache().await;
+        assert_eq!(fetcher.cache.lock().await.len(), 0);
+    }
 }
@@ -1,9 +1,16 @@
-use chrono::{Datelike, Local, NaiveDate, NaiveDateTime, NaiveTime, TimeZone};
+use chrono::{Datelike, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
 use color_eyre::{Result, eyre::eyre};
 use scraper::{Html, Selector};
 use tracing::{debug, warn};
 
-use crate::types::{EconomicEvent, Impact};
+use crate::types::{Currency, EconomicEvent, Impact};
+
+/// Timezone the scraped HTML's times are interpreted in when no explicit
+/// `source_tz` is available. Forex Factory doesn't stamp times with their
+/// zone, so absent a timezone cookie override this treats the scraped
+/// values as already being UTC.
+pub const DEFAULT_SOURCE_TZ: Tz = chrono_tz::UTC;
 
 /// Parses Forex Factory calendar HTML into structured events.
 pub struct CalendarParser {
@@ -46,9 +53,16 @@ impl CalendarParser {
         })
     }
 
-    /// Parse HTML content into a list of economic events.
+    /// Parse HTML content into a list of economic events. `source_tz` is the
+    /// timezone the scraped times are interpreted in before being converted
+    /// to the canonical UTC instant stored on `EconomicEvent`.
     /// The `base_date` is used as fallback and to determine the year for date parsing.
-    pub fn parse(&self, html: &str, base_date: NaiveDate) -> Result<Vec<EconomicEvent>> {
+    pub fn parse(
+        &self,
+        html: &str,
+        base_date: NaiveDate,
+        source_tz: Tz,
+    ) -> Result<Vec<EconomicEvent>> {
         debug!("Parsing HTML of {} bytes for date {base_date}", html.len());
         let document = Html::parse_document(html);
         let mut events = Vec::new();
@@ -60,7 +74,8 @@ impl CalendarParser {
         debug!("Found {row_count} event rows in HTML");
 
         for row in document.select(&self.row_selector) {
-            let event = self.parse_row(&row, &mut current_date, &mut current_time, reference_year);
+            let event =
+                self.parse_row(&row, &mut current_date, &mut current_time, reference_year, source_tz);
 
             match event {
                 Ok(Some(e)) => {
@@ -89,6 +104,7 @@ impl CalendarParser {
         current_date: &mut NaiveDate,
         current_time: &mut Option<NaiveTime>,
         reference_year: i32,
+        source_tz: Tz,
     ) -> Result<Option<EconomicEvent>> {
         // Update date if present in this row
         let date_text = self.extract_text(row, &self.date_selector);
@@ -99,10 +115,17 @@ impl CalendarParser {
             *current_time = None;
         }
 
-        let currency = self.extract_text(row, &self.currency_selector);
-        if currency.is_empty() {
+        let currency_text = self.extract_text(row, &self.currency_selector);
+        if currency_text.is_empty() {
             return Ok(None);
         }
+        let currency = match Currency::try_from(currency_text.as_str()) {
+            Ok(currency) => currency,
+            Err(e) => {
+                warn!("Skipping row with unrecognized currency '{currency_text}': {e}");
+                return Ok(None);
+            }
+        };
 
         let impact = self.extract_impact(row).unwrap_or(Impact::Low);
         let name = self.extract_text(row, &self.event_selector);
@@ -122,21 +145,38 @@ impl CalendarParser {
 
         let time = current_time.unwrap_or(NaiveTime::from_hms_opt(0, 0, 0).unwrap());
         let datetime = NaiveDateTime::new(*current_date, time);
-        // Forex Factory times are shown in user's local timezone
-        let datetime_local = Local
-            .from_local_datetime(&datetime)
-            .single()
-            .unwrap_or_else(|| Local.from_utc_datetime(&datetime));
+        // Interpret the scraped (zone-less) time in `source_tz`, then store
+        // the canonical UTC instant. A DST transition can make `datetime`
+        // ambiguous (repeated, on fall-back) or nonexistent (skipped, on
+        // spring-forward) in `source_tz`; rather than silently guessing,
+        // reject the row; `parse` skips it and logs a warning saying why.
+        let datetime_utc = match source_tz.from_local_datetime(&datetime) {
+            chrono::LocalResult::Single(dt) => dt.with_timezone(&Utc),
+            chrono::LocalResult::Ambiguous(earliest, latest) => {
+                return Err(eyre!(
+                    "Ambiguous local time {datetime} in {source_tz} (DST fall-back; could be {earliest} or {latest})"
+                ));
+            }
+            chrono::LocalResult::None => {
+                return Err(eyre!(
+                    "Nonexistent local time {datetime} in {source_tz} (DST spring-forward skips this time)"
+                ));
+            }
+        };
 
         let actual = self.extract_text(row, &self.actual_selector);
         let forecast = self.extract_text(row, &self.forecast_selector);
         let previous = self.extract_text(row, &self.previous_selector);
+        // Forex Factory renders bank holidays/market closures as ordinary
+        // rows named e.g. "Bank Holiday", timed "All Day"; there's no
+        // dedicated impact icon for it, so detect it from those two signals.
+        let is_holiday = name.to_lowercase().contains("holiday") || time_text == "All Day";
 
         Ok(Some(EconomicEvent {
             name,
             currency,
             impact,
-            datetime: datetime_local,
+            datetime: datetime_utc,
             actual: if actual.is_empty() {
                 None
             } else {
@@ -152,6 +192,12 @@ impl CalendarParser {
             } else {
                 Some(previous)
             },
+            is_holiday,
+            affected_currencies: if is_holiday {
+                Some(vec![currency])
+            } else {
+                None
+            },
         }))
     }
 
@@ -291,4 +337,86 @@ mod tests {
         // Whitespace only returns None
         assert_eq!(parse_date("   ", 2026), None);
     }
+
+    fn sample_row_html(date: &str, time: &str) -> String {
+        format!(
+            r#"<table><tr data-event-id="1">
+                <td class="calendar__date">{date}</td>
+                <td class="calendar__currency">USD</td>
+                <td class="calendar__impact"><span class="icon--ff-impact-red"></span></td>
+                <td class="calendar__event"><span class="calendar__event-title">Test Event</span></td>
+                <td class="calendar__time">{time}</td>
+                <td class="calendar__actual"></td>
+                <td class="calendar__forecast"></td>
+                <td class="calendar__previous"></td>
+            </tr></table>"#
+        )
+    }
+
+    #[test]
+    fn test_parse_rejects_nonexistent_local_time_on_dst_spring_forward() {
+        let parser = CalendarParser::new().unwrap();
+        // 2025-03-09 02:30 doesn't exist in America/New_York: clocks spring
+        // forward from 02:00 straight to 03:00.
+        let html = sample_row_html("Sun Mar 9", "02:30");
+        let events = parser
+            .parse(
+                &html,
+                NaiveDate::from_ymd_opt(2025, 3, 9).unwrap(),
+                chrono_tz::America::New_York,
+            )
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_parse_rejects_ambiguous_local_time_on_dst_fall_back() {
+        let parser = CalendarParser::new().unwrap();
+        // 2025-11-02 01:30 occurs twice in America/New_York: clocks fall
+        // back from 02:00 to 01:00.
+        let html = sample_row_html("Sun Nov 2", "01:30");
+        let events = parser
+            .parse(
+                &html,
+                NaiveDate::from_ymd_opt(2025, 11, 2).unwrap(),
+                chrono_tz::America::New_York,
+            )
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn test_parse_tags_bank_holiday_rows() {
+        let parser = CalendarParser::new().unwrap();
+        let html = r#"<table><tr data-event-id="1">
+                <td class="calendar__date">Mon Jun 2</td>
+                <td class="calendar__currency">JPY</td>
+                <td class="calendar__impact"></td>
+                <td class="calendar__event"><span class="calendar__event-title">Bank Holiday</span></td>
+                <td class="calendar__time">All Day</td>
+                <td class="calendar__actual"></td>
+                <td class="calendar__forecast"></td>
+                <td class="calendar__previous"></td>
+            </tr></table>"#;
+        let events = parser
+            .parse(html, NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(), DEFAULT_SOURCE_TZ)
+            .unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_holiday);
+        assert_eq!(events[0].affected_currencies, Some(vec![Currency::Jpy]));
+    }
+
+    #[test]
+    fn test_parse_accepts_unambiguous_local_time() {
+        let parser = CalendarParser::new().unwrap();
+        let html = sample_row_html("Mon Jun 2", "08:30");
+        let events = parser
+            .parse(
+                &html,
+                NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+                chrono_tz::America::New_York,
+            )
+            .unwrap();
+        assert_eq!(events.len(), 1);
+    }
 }
@@ -0,0 +1,3 @@
+mod calendar;
+
+pub use calendar::{CalendarService, DayEvents, group_by_day};
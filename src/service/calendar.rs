@@ -1,30 +1,171 @@
 use chrono::{Local, NaiveDate};
-use color_eyre::Result;
+use chrono_tz::Tz;
+use color_eyre::{Result, eyre::eyre};
+use dashmap::DashMap;
+use std::future::Future;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
-use tracing::info;
+use tracing::{debug, info};
 
-use crate::scraper::{CalendarParser, HttpCalendarFetcher};
+use crate::holidays::HolidayCalendar;
+use crate::scraper::{CalendarFetcher, CalendarParser, HttpCalendarFetcher};
 use crate::types::{EconomicEvent, EventQuery, Impact};
 
+/// Environment variable pointing at a JSON [`HolidayCalendar`] file to
+/// auto-load, mirroring `FOREX_CALENDAR_HTTP_ADDR`'s env-var-opt-in pattern
+/// in `main.rs`. Unset means no extra holiday annotations are applied.
+const HOLIDAYS_PATH_ENV: &str = "FOREX_CALENDAR_HOLIDAYS_PATH";
+
+/// Environment variable naming the IANA timezone the scraped site should be
+/// pinned to (via `HttpCalendarFetcher::with_source_tz`) and parsed as.
+/// Unset means the default fetcher is pinned to UTC.
+const SOURCE_TZ_ENV: &str = "FOREX_CALENDAR_SOURCE_TZ";
+
+/// How long parsed events for the current/future week are cached before
+/// being considered stale and re-fetched.
+const DEFAULT_CURRENT_WEEK_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// How long parsed events for a past (already-concluded) week are cached.
+/// Past weeks' actual/forecast/previous values are final, so they can be
+/// cached far longer than the current week.
+const DEFAULT_PAST_WEEK_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Events for a single calendar day, as returned by `get_agenda`.
+#[derive(Debug, Clone)]
+pub struct DayEvents {
+    /// The calendar date this bucket covers.
+    pub date: NaiveDate,
+    /// Events scheduled on this date, in chronological order.
+    pub events: Vec<EconomicEvent>,
+}
+
+/// A previously parsed week, kept so repeated queries for the same week
+/// skip both the network fetch and the HTML parse.
+struct CachedWeek {
+    events: Vec<EconomicEvent>,
+    fetched_at: Instant,
+}
+
 /// High-level service for fetching and querying economic events.
 pub struct CalendarService {
-    fetcher: Arc<Mutex<HttpCalendarFetcher>>,
+    fetcher: Arc<Mutex<dyn CalendarFetcher>>,
     parser: CalendarParser,
+    /// Timezone the fetcher's scraped wall-clock times are pinned to (see
+    /// [`CalendarFetcher::source_tz`]), used as the parser's `source_tz`.
+    source_tz: Tz,
+    event_cache: DashMap<String, CachedWeek>,
+    current_week_ttl: Duration,
+    past_week_ttl: Duration,
+    holiday_calendar: Option<HolidayCalendar>,
 }
 
 impl CalendarService {
-    /// Create a new calendar service.
+    /// Create a new calendar service backed by [`HttpCalendarFetcher`]. If
+    /// `FOREX_CALENDAR_SOURCE_TZ` is set, the fetcher is pinned to that IANA
+    /// timezone instead of UTC (see [`HttpCalendarFetcher::with_source_tz`]).
+    /// If `FOREX_CALENDAR_HOLIDAYS_PATH` is set, its holiday calendar is
+    /// loaded and applied to every query.
     pub fn new() -> Result<Self> {
-        let fetcher = HttpCalendarFetcher::new()?;
+        let fetcher = match std::env::var(SOURCE_TZ_ENV) {
+            Ok(tz_name) => {
+                let tz: Tz = tz_name
+                    .parse()
+                    .map_err(|_| eyre!("Invalid {SOURCE_TZ_ENV} value '{tz_name}'"))?;
+                HttpCalendarFetcher::with_source_tz(tz)?
+            }
+            Err(_) => HttpCalendarFetcher::new()?,
+        };
+
+        let mut service = Self::with_fetcher(fetcher)?;
+        if let Ok(path) = std::env::var(HOLIDAYS_PATH_ENV) {
+            service = service.with_holiday_calendar(HolidayCalendar::load_from_file(path)?);
+        }
+        Ok(service)
+    }
+
+    /// Create a calendar service around an arbitrary [`CalendarFetcher`]
+    /// implementation — e.g. a mock in tests, or a fetcher backed by a
+    /// headless browser or challenge-solving proxy. Events are parsed using
+    /// `fetcher.source_tz()` as the source timezone.
+    pub fn with_fetcher(fetcher: impl CalendarFetcher + 'static) -> Result<Self> {
         let parser = CalendarParser::new()?;
+        let source_tz = fetcher.source_tz();
 
         Ok(Self {
             fetcher: Arc::new(Mutex::new(fetcher)),
             parser,
+            source_tz,
+            event_cache: DashMap::new(),
+            current_week_ttl: DEFAULT_CURRENT_WEEK_TTL,
+            past_week_ttl: DEFAULT_PAST_WEEK_TTL,
+            holiday_calendar: None,
         })
     }
 
+    /// Attach a [`HolidayCalendar`] whose closures are merged into every
+    /// query's results.
+    pub fn with_holiday_calendar(mut self, holiday_calendar: HolidayCalendar) -> Self {
+        self.holiday_calendar = Some(holiday_calendar);
+        self
+    }
+
+    /// Override the parsed-events cache TTLs: `current_week_ttl` applies to
+    /// the week containing today (and any future week), `past_week_ttl`
+    /// applies to weeks that have already concluded.
+    pub fn with_cache_ttls(mut self, current_week_ttl: Duration, past_week_ttl: Duration) -> Self {
+        self.current_week_ttl = current_week_ttl;
+        self.past_week_ttl = past_week_ttl;
+        self
+    }
+
+    /// Drop all cached parsed events, forcing the next query for each week
+    /// to re-fetch and re-parse.
+    pub fn clear_cache(&self) {
+        self.event_cache.clear();
+    }
+
+    /// Get parsed events for `key`, serving from cache within the TTL for
+    /// `reference_date` (past week vs. current/future week), or otherwise
+    /// awaiting `fetch_html` and parsing the result with `reference_date`
+    /// as the parser's base date.
+    async fn cached_parse<F, Fut>(
+        &self,
+        key: &str,
+        reference_date: NaiveDate,
+        fetch_html: F,
+    ) -> Result<Vec<EconomicEvent>>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<String>>,
+    {
+        let ttl = if reference_date < Local::now().date_naive() {
+            self.past_week_ttl
+        } else {
+            self.current_week_ttl
+        };
+
+        if let Some(cached) = self.event_cache.get(key)
+            && cached.fetched_at.elapsed() < ttl
+        {
+            debug!("Serving parsed events for '{key}' from cache (within {ttl:?} TTL)");
+            return Ok(cached.events.clone());
+        }
+
+        let html = fetch_html().await?;
+        let events = self.parser.parse(&html, reference_date, self.source_tz)?;
+
+        self.event_cache.insert(
+            key.to_string(),
+            CachedWeek {
+                events: events.clone(),
+                fetched_at: Instant::now(),
+            },
+        );
+
+        Ok(events)
+    }
+
     /// Query events matching the given criteria.
     pub async fn query_events(&self, query: &EventQuery) -> Result<Vec<EconomicEvent>> {
         // Determine which date to fetch
@@ -32,13 +173,18 @@ impl CalendarService {
 
         info!("Fetching calendar for date: {base_date}");
 
-        // Fetch HTML from Forex Factory
-        let fetcher = self.fetcher.lock().await;
-        let html = fetcher.fetch_date(base_date).await?;
-        drop(fetcher); // Release lock early
+        let key = format!("date:{base_date}");
+        let events = self
+            .cached_parse(&key, base_date, || async {
+                let fetcher = self.fetcher.lock().await;
+                fetcher.fetch_date(base_date).await
+            })
+            .await?;
 
-        // Parse events
-        let events = self.parser.parse(&html, base_date)?;
+        let events = match &self.holiday_calendar {
+            Some(holiday_calendar) => holiday_calendar.annotate(events),
+            None => events,
+        };
 
         // Filter events based on query
         let min_impact = query.min_impact.unwrap_or(Impact::Low);
@@ -47,38 +193,405 @@ impl CalendarService {
             .filter(|e| e.meets_impact(min_impact))
             .filter(|e| e.matches_currencies(&query.currencies))
             .filter(|e| query.datetime_in_range(&e.datetime))
+            .filter(|e| !query.exclude_holidays || !e.is_holiday)
             .collect();
 
         info!("Found {} events matching query", filtered.len());
         Ok(filtered)
     }
 
+    /// Export events matching the given criteria as an RFC 5545 iCalendar feed.
+    pub async fn export_ics(&self, query: &EventQuery) -> Result<String> {
+        let events = self.query_events(query).await?;
+        Ok(crate::ical::to_ics(&events))
+    }
+
+    /// Query events matching the given criteria and group them into day
+    /// buckets for an agenda-style view.
+    ///
+    /// Events are sorted by `datetime` first, then folded into buckets in a
+    /// single pass: a new bucket starts whenever the naive date changes.
+    pub async fn get_agenda(&self, query: &EventQuery) -> Result<Vec<DayEvents>> {
+        let events = self.query_events(query).await?;
+        Ok(group_by_day(events, query.display_tz))
+    }
+
     /// Get events for today.
     pub async fn get_today_events(&self) -> Result<Vec<EconomicEvent>> {
         let today = Local::now().date_naive();
-        let fetcher = self.fetcher.lock().await;
-        let html = fetcher.fetch_today().await?;
-        drop(fetcher);
-
-        self.parser.parse(&html, today)
+        self.cached_parse("today", today, || async {
+            let fetcher = self.fetcher.lock().await;
+            fetcher.fetch_today().await
+        })
+        .await
     }
 
     /// Get events for this week.
     pub async fn get_week_events(&self) -> Result<Vec<EconomicEvent>> {
         let today = Local::now().date_naive();
-        let fetcher = self.fetcher.lock().await;
-        let html = fetcher.fetch_this_week().await?;
-        drop(fetcher);
-
-        self.parser.parse(&html, today)
+        self.cached_parse("this_week", today, || async {
+            let fetcher = self.fetcher.lock().await;
+            fetcher.fetch_this_week().await
+        })
+        .await
     }
 
     /// Get events for a specific week containing the given date.
     pub async fn get_week_events_for(&self, date: NaiveDate) -> Result<Vec<EconomicEvent>> {
-        let fetcher = self.fetcher.lock().await;
-        let html = fetcher.fetch_date(date).await?;
-        drop(fetcher);
+        let key = format!("date:{date}");
+        self.cached_parse(&key, date, || async {
+            let fetcher = self.fetcher.lock().await;
+            fetcher.fetch_date(date).await
+        })
+        .await
+    }
+}
+
+/// Group events into day buckets: sort by `datetime`, then start a new
+/// bucket whenever the naive date (in `display_tz`, or UTC if unset) changes.
+pub fn group_by_day(mut events: Vec<EconomicEvent>, display_tz: Option<Tz>) -> Vec<DayEvents> {
+    events.sort_by_key(|e| e.datetime);
+    let tz = display_tz.unwrap_or(Tz::UTC);
+
+    let mut days: Vec<DayEvents> = Vec::new();
+    for event in events {
+        let date = event.datetime.with_timezone(&tz).date_naive();
+        match days.last_mut() {
+            Some(day) if day.date == date => day.events.push(event),
+            _ => days.push(DayEvents {
+                date,
+                events: vec![event],
+            }),
+        }
+    }
+    days
+}
+
+/// Fill in empty [`DayEvents`] buckets for every date in `[from, to]` that
+/// `days` doesn't already cover, so an agenda view can show the gap instead
+/// of silently skipping days with nothing scheduled. `days` need not be
+/// sorted or restricted to the range; the result always is.
+pub fn fill_gaps(days: Vec<DayEvents>, from: NaiveDate, to: NaiveDate) -> Vec<DayEvents> {
+    let mut by_date: std::collections::HashMap<NaiveDate, Vec<EconomicEvent>> =
+        days.into_iter().map(|d| (d.date, d.events)).collect();
+
+    let mut date = from;
+    let mut filled = Vec::new();
+    while date <= to {
+        let events = by_date.remove(&date).unwrap_or_default();
+        filled.push(DayEvents { date, events });
+        date += chrono::Duration::days(1);
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Currency, Impact};
+    use chrono::{TimeZone, Utc};
+
+    fn sample_event(day: u32, hour: u32) -> EconomicEvent {
+        EconomicEvent {
+            name: "Test Event".to_string(),
+            currency: Currency::Usd,
+            impact: Impact::High,
+            datetime: Utc.with_ymd_and_hms(2025, 6, day, hour, 0, 0).unwrap(),
+            actual: None,
+            forecast: None,
+            previous: None,
+            is_holiday: false,
+            affected_currencies: None,
+        }
+    }
+
+    #[test]
+    fn test_group_by_day_buckets_consecutive_events() {
+        let events = vec![
+            sample_event(2, 8),
+            sample_event(2, 14),
+            sample_event(3, 9),
+        ];
+
+        let days = group_by_day(events, None);
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2025, 6, 2).unwrap());
+        assert_eq!(days[0].events.len(), 2);
+        assert_eq!(days[1].date, NaiveDate::from_ymd_opt(2025, 6, 3).unwrap());
+        assert_eq!(days[1].events.len(), 1);
+    }
+
+    #[test]
+    fn test_group_by_day_sorts_out_of_order_input() {
+        let events = vec![sample_event(3, 9), sample_event(2, 8)];
+        let days = group_by_day(events, None);
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2025, 6, 2).unwrap());
+        assert_eq!(days[1].date, NaiveDate::from_ymd_opt(2025, 6, 3).unwrap());
+    }
+
+    #[test]
+    fn test_group_by_day_empty_input() {
+        assert!(group_by_day(vec![], None).is_empty());
+    }
+
+    #[test]
+    fn test_group_by_day_buckets_by_display_tz() {
+        // 2025-06-02 02:00 UTC is still 2025-06-01 evening in Eastern time.
+        let event = sample_event(2, 2);
+        let days = group_by_day(vec![event], Some(chrono_tz::America::New_York));
+        assert_eq!(days[0].date, NaiveDate::from_ymd_opt(2025, 6, 1).unwrap());
+    }
+
+    #[test]
+    fn test_fill_gaps_inserts_empty_days() {
+        let days = group_by_day(vec![sample_event(2, 8), sample_event(4, 9)], None);
+        let filled = fill_gaps(
+            days,
+            NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 6, 4).unwrap(),
+        );
+        assert_eq!(filled.len(), 3);
+        assert_eq!(filled[0].events.len(), 1);
+        assert!(filled[1].events.is_empty());
+        assert_eq!(filled[1].date, NaiveDate::from_ymd_opt(2025, 6, 3).unwrap());
+        assert_eq!(filled[2].events.len(), 1);
+    }
+
+    #[test]
+    fn test_fill_gaps_empty_input_covers_whole_range() {
+        let filled = fill_gaps(
+            vec![],
+            NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 6, 3).unwrap(),
+        );
+        assert_eq!(filled.len(), 2);
+        assert!(filled.iter().all(|d| d.events.is_empty()));
+    }
+
+    #[test]
+    fn test_with_cache_ttls_overrides_defaults() {
+        let service = CalendarService::new()
+            .unwrap()
+            .with_cache_ttls(Duration::from_secs(1), Duration::from_secs(2));
+        assert_eq!(service.current_week_ttl, Duration::from_secs(1));
+        assert_eq!(service.past_week_ttl, Duration::from_secs(2));
+    }
+
+    #[tokio::test]
+    async fn test_cached_parse_serves_cache_hit_without_refetching() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let service = CalendarService::new().unwrap();
+        let today = Local::now().date_naive();
+        let calls = AtomicUsize::new(0);
+
+        for _ in 0..2 {
+            service
+                .cached_parse("test-key", today, || {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    async { Ok("<html></html>".to_string()) }
+                })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_clear_cache_forces_refetch() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let service = CalendarService::new().unwrap();
+        let today = Local::now().date_naive();
+        let calls = AtomicUsize::new(0);
+
+        service
+            .cached_parse("test-key", today, || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok("<html></html>".to_string()) }
+            })
+            .await
+            .unwrap();
+
+        service.clear_cache();
+
+        service
+            .cached_parse("test-key", today, || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Ok("<html></html>".to_string()) }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_cached_parse_uses_past_week_ttl_for_past_dates() {
+        let service = CalendarService::new()
+            .unwrap()
+            .with_cache_ttls(Duration::from_secs(0), Duration::from_secs(3600));
+        let past_date = NaiveDate::from_ymd_opt(2000, 1, 1).unwrap();
+
+        service
+            .cached_parse(
+                "past-key",
+                past_date,
+                || async { Ok("<html></html>".to_string()) },
+            )
+            .await
+            .unwrap();
+
+        // current_week_ttl is 0s, but past_date should use the 1h past_week_ttl,
+        // so a second call within that window must hit the cache, not panic on
+        // a real network fetch.
+        let events = service
+            .cached_parse("past-key", past_date, || async {
+                panic!("should not refetch a cached past week");
+                #[allow(unreachable_code)]
+                Ok(String::new())
+            })
+            .await
+            .unwrap();
+        assert!(events.is_empty());
+    }
+
+    /// A fetcher stub that always returns the same canned HTML, used to
+    /// prove `CalendarService` can be driven by something other than
+    /// `HttpCalendarFetcher` (e.g. in tests, or a browser-backed fetcher).
+    struct MockFetcher {
+        html: String,
+    }
+
+    #[async_trait::async_trait]
+    impl CalendarFetcher for MockFetcher {
+        async fn fetch_week(&self, _week: &str) -> Result<String> {
+            Ok(self.html.clone())
+        }
+
+        async fn fetch_today(&self) -> Result<String> {
+            Ok(self.html.clone())
+        }
+
+        async fn fetch_this_week(&self) -> Result<String> {
+            Ok(self.html.clone())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_fetcher_allows_mock_injection() {
+        let service = CalendarService::with_fetcher(MockFetcher {
+            html: "<html></html>".to_string(),
+        })
+        .unwrap();
+
+        let events = service.get_today_events().await.unwrap();
+        assert!(events.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_query_events_annotates_with_holiday_calendar() {
+        let service = CalendarService::with_fetcher(MockFetcher {
+            html: "<html></html>".to_string(),
+        })
+        .unwrap()
+        .with_holiday_calendar(
+            HolidayCalendar::from_json(
+                r#"{"USD": [{"date": "2025-06-02", "name": "Independence Day"}]}"#,
+            )
+            .unwrap(),
+        );
+
+        let query = EventQuery::new().with_date_range(
+            NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+        );
+        let events = service.query_events(&query).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert!(events[0].is_holiday);
+    }
+
+    #[tokio::test]
+    async fn test_query_events_exclude_holidays_drops_holiday_events() {
+        let service = CalendarService::with_fetcher(MockFetcher {
+            html: "<html></html>".to_string(),
+        })
+        .unwrap()
+        .with_holiday_calendar(
+            HolidayCalendar::from_json(
+                r#"{"USD": [{"date": "2025-06-02", "name": "Independence Day"}]}"#,
+            )
+            .unwrap(),
+        );
+
+        let query = EventQuery::new()
+            .with_date_range(
+                NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+            )
+            .with_exclude_holidays(true);
+        let events = service.query_events(&query).await.unwrap();
+        assert!(events.is_empty());
+    }
+
+    /// A fetcher stub that reports a fixed, non-default `source_tz`, used to
+    /// prove `CalendarService` parses with the fetcher's pinned timezone
+    /// rather than hardcoding UTC.
+    struct TimezonePinnedFetcher {
+        html: String,
+        tz: Tz,
+    }
+
+    #[async_trait::async_trait]
+    impl CalendarFetcher for TimezonePinnedFetcher {
+        async fn fetch_week(&self, _week: &str) -> Result<String> {
+            Ok(self.html.clone())
+        }
+
+        async fn fetch_today(&self) -> Result<String> {
+            Ok(self.html.clone())
+        }
+
+        async fn fetch_this_week(&self) -> Result<String> {
+            Ok(self.html.clone())
+        }
+
+        fn source_tz(&self) -> Tz {
+            self.tz
+        }
+    }
+
+    #[tokio::test]
+    async fn test_with_fetcher_parses_using_fetcher_source_tz() {
+        // 2025-06-02 09:00 America/New_York is 13:00 UTC.
+        let html = r#"<table><tr data-event-id="1">
+                <td class="calendar__date">Mon Jun 2</td>
+                <td class="calendar__currency">USD</td>
+                <td class="calendar__impact"><span class="icon--ff-impact-red"></span></td>
+                <td class="calendar__event"><span class="calendar__event-title">Test Event</span></td>
+                <td class="calendar__time">9:00am</td>
+                <td class="calendar__actual"></td>
+                <td class="calendar__forecast"></td>
+                <td class="calendar__previous"></td>
+            </tr></table>"#
+            .to_string();
+
+        let service = CalendarService::with_fetcher(TimezonePinnedFetcher {
+            html,
+            tz: chrono_tz::America::New_York,
+        })
+        .unwrap();
 
-        self.parser.parse(&html, date)
+        let query = EventQuery::new().with_date_range(
+            NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 6, 2).unwrap(),
+        );
+        let events = service.query_events(&query).await.unwrap();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].datetime,
+            chrono::Utc.with_ymd_and_hms(2025, 6, 2, 13, 0, 0).unwrap()
+        );
     }
 }
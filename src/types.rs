@@ -1,9 +1,13 @@
+mod calendar_query;
 mod currency;
 mod event;
 mod impact;
 mod query;
+mod surprise;
 
+pub use calendar_query::*;
 pub use currency::*;
 pub use event::*;
 pub use impact::*;
 pub use query::*;
+pub use surprise::*;
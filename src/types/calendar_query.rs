@@ -0,0 +1,106 @@
+//! Aggregation helpers over an already-fetched batch of events, mirroring
+//! the events-by-currency / events-by-day / value-by-id split common to
+//! programmatic economic-calendar APIs.
+//!
+//! This is distinct from [`super::EventQuery`], which describes *what to
+//! fetch* from the scraper; these functions group and look up events the
+//! caller already has in hand.
+
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
+use super::{Currency, EconomicEvent};
+
+/// Find a single event by its [`EconomicEvent::id`].
+pub fn find_by_id<'a>(events: &'a [EconomicEvent], id: &str) -> Option<&'a EconomicEvent> {
+    events.iter().find(|e| e.id() == id)
+}
+
+/// Group events by currency, each group sorted chronologically.
+pub fn group_by_currency(mut events: Vec<EconomicEvent>) -> HashMap<Currency, Vec<EconomicEvent>> {
+    events.sort_by_key(|e| e.datetime);
+    let mut groups: HashMap<Currency, Vec<EconomicEvent>> = HashMap::new();
+    for event in events {
+        groups.entry(event.currency).or_default().push(event);
+    }
+    groups
+}
+
+/// Group events by UTC calendar date, each group sorted chronologically.
+///
+/// This is the types-layer counterpart to [`crate::service::group_by_day`],
+/// which additionally buckets by a display timezone for the agenda view;
+/// use this one when you just need events grouped by their UTC date.
+pub fn group_by_day(mut events: Vec<EconomicEvent>) -> HashMap<NaiveDate, Vec<EconomicEvent>> {
+    events.sort_by_key(|e| e.datetime);
+    let mut groups: HashMap<NaiveDate, Vec<EconomicEvent>> = HashMap::new();
+    for event in events {
+        groups
+            .entry(event.datetime.date_naive())
+            .or_default()
+            .push(event);
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Impact;
+    use chrono::{TimeZone, Utc};
+
+    fn sample_event(currency: Currency, impact: Impact, day: u32, hour: u32) -> EconomicEvent {
+        EconomicEvent {
+            name: "Test Event".to_string(),
+            currency,
+            impact,
+            datetime: Utc.with_ymd_and_hms(2025, 6, day, hour, 0, 0).unwrap(),
+            actual: None,
+            forecast: None,
+            previous: None,
+            is_holiday: false,
+            affected_currencies: None,
+        }
+    }
+
+    #[test]
+    fn test_find_by_id() {
+        let events = vec![
+            sample_event(Currency::Usd, Impact::High, 2, 8),
+            sample_event(Currency::Eur, Impact::High, 2, 9),
+        ];
+        let target_id = events[1].id();
+        let found = find_by_id(&events, &target_id).unwrap();
+        assert_eq!(found.currency, Currency::Eur);
+        assert!(find_by_id(&events, "bogus").is_none());
+    }
+
+    #[test]
+    fn test_group_by_currency() {
+        let events = vec![
+            sample_event(Currency::Usd, Impact::High, 2, 8),
+            sample_event(Currency::Eur, Impact::High, 2, 9),
+            sample_event(Currency::Usd, Impact::High, 3, 8),
+        ];
+        let groups = group_by_currency(events);
+        assert_eq!(groups[&Currency::Usd].len(), 2);
+        assert_eq!(groups[&Currency::Eur].len(), 1);
+        assert!(groups[&Currency::Usd][0].datetime < groups[&Currency::Usd][1].datetime);
+    }
+
+    #[test]
+    fn test_group_by_day() {
+        let events = vec![
+            sample_event(Currency::Usd, Impact::High, 2, 8),
+            sample_event(Currency::Usd, Impact::High, 2, 14),
+            sample_event(Currency::Usd, Impact::High, 3, 8),
+        ];
+        let groups = group_by_day(events);
+        assert_eq!(groups.len(), 2);
+        assert_eq!(
+            groups[&NaiveDate::from_ymd_opt(2025, 6, 2).unwrap()].len(),
+            2
+        );
+    }
+}
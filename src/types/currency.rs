@@ -1,77 +1,158 @@
-//! Currency code resolution from country names and abbreviations.
+//! Typed ISO 4217 currency codes, resolved from country names and
+//! abbreviations.
 //!
-//! Supports resolving inputs like "Canada", "Canadian", "CAD" all to "CAD".
+//! Supports resolving inputs like "Canada", "Canadian", "CAD" all to
+//! [`Currency::Cad`].
 
 use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::LazyLock;
 
-/// Static mapping of country names/variations to ISO 4217 currency codes.
+use serde::{Deserialize, Serialize};
+
+/// An ISO 4217 currency code covered by the Forex Factory calendar.
+///
+/// `CNH` (offshore yuan) is an alias that resolves to [`Currency::Cny`];
+/// Forex Factory doesn't distinguish the two on the calendar page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum Currency {
+    Usd,
+    Eur,
+    Gbp,
+    Jpy,
+    Aud,
+    Cad,
+    Chf,
+    Nzd,
+    Cny,
+}
+
+/// Error returned when an input doesn't match any known currency code,
+/// country name, or demonym.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownCurrencyError(String);
+
+impl fmt::Display for UnknownCurrencyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "unknown currency: {}", self.0)
+    }
+}
+
+impl std::error::Error for UnknownCurrencyError {}
+
+/// Static mapping of country names/variations to currencies.
 /// Keys are lowercase for case-insensitive lookup.
-static COUNTRY_TO_CURRENCY: LazyLock<HashMap<&'static str, &'static str>> = LazyLock::new(|| {
+static COUNTRY_TO_CURRENCY: LazyLock<HashMap<&'static str, Currency>> = LazyLock::new(|| {
     HashMap::from([
         // USD - United States Dollar
-        ("usd", "USD"),
-        ("united states", "USD"),
-        ("usa", "USD"),
-        ("us", "USD"),
-        ("america", "USD"),
-        ("american", "USD"),
+        ("usd", Currency::Usd),
+        ("united states", Currency::Usd),
+        ("usa", Currency::Usd),
+        ("us", Currency::Usd),
+        ("america", Currency::Usd),
+        ("american", Currency::Usd),
         // EUR - Euro
-        ("eur", "EUR"),
-        ("euro", "EUR"),
-        ("eurozone", "EUR"),
-        ("european", "EUR"),
+        ("eur", Currency::Eur),
+        ("euro", Currency::Eur),
+        ("eurozone", Currency::Eur),
+        ("european", Currency::Eur),
         // GBP - British Pound
-        ("gbp", "GBP"),
-        ("united kingdom", "GBP"),
-        ("uk", "GBP"),
-        ("britain", "GBP"),
-        ("british", "GBP"),
-        ("england", "GBP"),
-        ("english", "GBP"),
+        ("gbp", Currency::Gbp),
+        ("united kingdom", Currency::Gbp),
+        ("uk", Currency::Gbp),
+        ("britain", Currency::Gbp),
+        ("british", Currency::Gbp),
+        ("england", Currency::Gbp),
+        ("english", Currency::Gbp),
         // JPY - Japanese Yen
-        ("jpy", "JPY"),
-        ("japan", "JPY"),
-        ("japanese", "JPY"),
+        ("jpy", Currency::Jpy),
+        ("japan", Currency::Jpy),
+        ("japanese", Currency::Jpy),
         // AUD - Australian Dollar
-        ("aud", "AUD"),
-        ("australia", "AUD"),
-        ("australian", "AUD"),
+        ("aud", Currency::Aud),
+        ("australia", Currency::Aud),
+        ("australian", Currency::Aud),
         // CAD - Canadian Dollar
-        ("cad", "CAD"),
-        ("canada", "CAD"),
-        ("canadian", "CAD"),
+        ("cad", Currency::Cad),
+        ("canada", Currency::Cad),
+        ("canadian", Currency::Cad),
         // CHF - Swiss Franc
-        ("chf", "CHF"),
-        ("switzerland", "CHF"),
-        ("swiss", "CHF"),
+        ("chf", Currency::Chf),
+        ("switzerland", Currency::Chf),
+        ("swiss", Currency::Chf),
         // NZD - New Zealand Dollar
-        ("nzd", "NZD"),
-        ("new zealand", "NZD"),
-        ("kiwi", "NZD"),
+        ("nzd", Currency::Nzd),
+        ("new zealand", Currency::Nzd),
+        ("kiwi", Currency::Nzd),
         // CNY/CNH - Chinese Yuan
-        ("cny", "CNY"),
-        ("cnh", "CNY"),
-        ("china", "CNY"),
-        ("chinese", "CNY"),
+        ("cny", Currency::Cny),
+        ("cnh", Currency::Cny),
+        ("china", Currency::Cny),
+        ("chinese", Currency::Cny),
     ])
 });
 
-/// Resolve a currency input to its ISO 4217 code.
+impl TryFrom<&str> for Currency {
+    type Error = UnknownCurrencyError;
+
+    /// Accepts currency codes ("USD"), country names ("Canada"), and
+    /// demonyms ("Canadian"), case-insensitively and trimmed.
+    fn try_from(input: &str) -> Result<Self, Self::Error> {
+        let normalized = input.trim().to_lowercase();
+        COUNTRY_TO_CURRENCY
+            .get(normalized.as_str())
+            .copied()
+            .ok_or_else(|| UnknownCurrencyError(input.trim().to_string()))
+    }
+}
+
+impl FromStr for Currency {
+    type Err = UnknownCurrencyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Currency::try_from(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Currency::try_from(s.as_str()).map_err(serde::de::Error::custom)
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let code = match self {
+            Currency::Usd => "USD",
+            Currency::Eur => "EUR",
+            Currency::Gbp => "GBP",
+            Currency::Jpy => "JPY",
+            Currency::Aud => "AUD",
+            Currency::Cad => "CAD",
+            Currency::Chf => "CHF",
+            Currency::Nzd => "NZD",
+            Currency::Cny => "CNY",
+        };
+        write!(f, "{code}")
+    }
+}
+
+/// Resolve a currency input to its typed ISO 4217 currency.
 ///
 /// Accepts:
-/// - Currency codes: "USD", "usd" → "USD"
-/// - Country names: "Canada", "CANADA" → "CAD"
-/// - Demonyms: "Canadian", "american" → respective codes
+/// - Currency codes: "USD", "usd" → `Currency::Usd`
+/// - Country names: "Canada", "CANADA" → `Currency::Cad`
+/// - Demonyms: "Canadian", "american" → respective currencies
 ///
-/// Returns the input uppercased if no mapping found (for backwards compatibility).
-pub fn resolve_currency(input: &str) -> String {
-    let normalized = input.trim().to_lowercase();
-
-    COUNTRY_TO_CURRENCY
-        .get(normalized.as_str())
-        .map(|&code| code.to_string())
-        .unwrap_or_else(|| input.trim().to_uppercase())
+/// Returns `Err(UnknownCurrencyError)` for unrecognized input.
+pub fn resolve_currency(input: &str) -> Result<Currency, UnknownCurrencyError> {
+    Currency::try_from(input)
 }
 
 #[cfg(test)]
@@ -80,39 +161,61 @@ mod tests {
 
     #[test]
     fn test_currency_codes_pass_through() {
-        assert_eq!(resolve_currency("USD"), "USD");
-        assert_eq!(resolve_currency("usd"), "USD");
-        assert_eq!(resolve_currency("cad"), "CAD");
-        assert_eq!(resolve_currency("EUR"), "EUR");
+        assert_eq!(resolve_currency("USD"), Ok(Currency::Usd));
+        assert_eq!(resolve_currency("usd"), Ok(Currency::Usd));
+        assert_eq!(resolve_currency("cad"), Ok(Currency::Cad));
+        assert_eq!(resolve_currency("EUR"), Ok(Currency::Eur));
     }
 
     #[test]
     fn test_country_names_resolve() {
-        assert_eq!(resolve_currency("Canada"), "CAD");
-        assert_eq!(resolve_currency("CANADA"), "CAD");
-        assert_eq!(resolve_currency("canada"), "CAD");
-        assert_eq!(resolve_currency("United States"), "USD");
-        assert_eq!(resolve_currency("Japan"), "JPY");
-        assert_eq!(resolve_currency("Australia"), "AUD");
+        assert_eq!(resolve_currency("Canada"), Ok(Currency::Cad));
+        assert_eq!(resolve_currency("CANADA"), Ok(Currency::Cad));
+        assert_eq!(resolve_currency("canada"), Ok(Currency::Cad));
+        assert_eq!(resolve_currency("United States"), Ok(Currency::Usd));
+        assert_eq!(resolve_currency("Japan"), Ok(Currency::Jpy));
+        assert_eq!(resolve_currency("Australia"), Ok(Currency::Aud));
     }
 
     #[test]
     fn test_demonyms_resolve() {
-        assert_eq!(resolve_currency("Canadian"), "CAD");
-        assert_eq!(resolve_currency("American"), "USD");
-        assert_eq!(resolve_currency("Japanese"), "JPY");
+        assert_eq!(resolve_currency("Canadian"), Ok(Currency::Cad));
+        assert_eq!(resolve_currency("American"), Ok(Currency::Usd));
+        assert_eq!(resolve_currency("Japanese"), Ok(Currency::Jpy));
+    }
+
+    #[test]
+    fn test_cnh_alias_resolves_to_cny() {
+        assert_eq!(resolve_currency("CNH"), Ok(Currency::Cny));
+        assert_eq!(resolve_currency("cnh"), Ok(Currency::Cny));
     }
 
     #[test]
-    fn test_unknown_input_uppercased() {
-        // Unknown inputs should just be uppercased (backwards compatible)
-        assert_eq!(resolve_currency("xyz"), "XYZ");
-        assert_eq!(resolve_currency("Unknown"), "UNKNOWN");
+    fn test_unknown_input_is_an_error() {
+        assert!(resolve_currency("xyz").is_err());
+        assert!(resolve_currency("Unknown").is_err());
     }
 
     #[test]
     fn test_whitespace_handling() {
-        assert_eq!(resolve_currency("  USD  "), "USD");
-        assert_eq!(resolve_currency("  canada  "), "CAD");
+        assert_eq!(resolve_currency("  USD  "), Ok(Currency::Usd));
+        assert_eq!(resolve_currency("  canada  "), Ok(Currency::Cad));
+    }
+
+    #[test]
+    fn test_display_round_trips_through_from_str() {
+        for currency in [
+            Currency::Usd,
+            Currency::Eur,
+            Currency::Gbp,
+            Currency::Jpy,
+            Currency::Aud,
+            Currency::Cad,
+            Currency::Chf,
+            Currency::Nzd,
+            Currency::Cny,
+        ] {
+            assert_eq!(currency.to_string().parse::<Currency>(), Ok(currency));
+        }
     }
 }
@@ -1,7 +1,11 @@
-use chrono::{DateTime, Local};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use chrono::{DateTime, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
-use super::Impact;
+use super::{Currency, Impact, Surprise, surprise};
 
 /// An economic event from the Forex Factory calendar.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -9,14 +13,15 @@ pub struct EconomicEvent {
     /// Event title (e.g., "Non-Farm Payrolls", "Interest Rate Decision")
     pub name: String,
 
-    /// Currency affected (e.g., "USD", "EUR", "AUD")
-    pub currency: String,
+    /// Currency affected
+    pub currency: Currency,
 
     /// Impact level on the market
     pub impact: Impact,
 
-    /// Scheduled date and time of the event (local timezone)
-    pub datetime: DateTime<Local>,
+    /// Scheduled instant of the event, stored as the canonical UTC instant.
+    /// Use [`EconomicEvent::in_timezone`] to project it into a display zone.
+    pub datetime: DateTime<Utc>,
 
     /// Actual value (if released)
     pub actual: Option<String>,
@@ -26,9 +31,27 @@ pub struct EconomicEvent {
 
     /// Previous period's value
     pub previous: Option<String>,
+
+    /// Whether this event represents a bank holiday / market closure rather
+    /// than a scheduled release, either because Forex Factory itself labeled
+    /// the row as a holiday or because a [`crate::holidays::HolidayCalendar`]
+    /// tagged it at query time.
+    #[serde(default)]
+    pub is_holiday: bool,
+
+    /// For holiday events, the currencies whose markets are closed. `None`
+    /// for ordinary scheduled releases.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub affected_currencies: Option<Vec<Currency>>,
 }
 
 impl EconomicEvent {
+    /// Project this event's instant into an arbitrary IANA timezone, e.g.
+    /// `chrono_tz::America::New_York` or `chrono_tz::Asia::Tokyo`.
+    pub fn in_timezone(&self, tz: Tz) -> DateTime<Tz> {
+        self.datetime.with_timezone(&tz)
+    }
+
     /// Check if this event matches the given minimum impact level
     pub fn meets_impact(&self, min_impact: Impact) -> bool {
         self.impact >= min_impact
@@ -39,34 +62,53 @@ impl EconomicEvent {
         if currencies.is_empty() {
             return true;
         }
-        currencies
-            .iter()
-            .any(|c| c.eq_ignore_ascii_case(&self.currency))
+        let code = self.currency.to_string();
+        currencies.iter().any(|c| c.eq_ignore_ascii_case(&code))
+    }
+
+    /// Compute how the actual value compared to the forecast, e.g. "NFP
+    /// beat forecast by 55K". Returns `None` if either value is absent or
+    /// not numeric.
+    pub fn surprise(&self) -> Option<Surprise> {
+        surprise(self.actual.as_deref(), self.forecast.as_deref())
+    }
+
+    /// Stable identifier derived from this event's currency, name, and
+    /// datetime, so a client can reference a specific event (e.g. to look it
+    /// up again later even after its `actual` value has been filled in).
+    pub fn id(&self) -> String {
+        let mut hasher = DefaultHasher::new();
+        self.currency.hash(&mut hasher);
+        self.name.hash(&mut hasher);
+        self.datetime.hash(&mut hasher);
+        format!("{:016x}", hasher.finish())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::TimeZone;
+    use chrono::{TimeZone, Timelike};
 
-    fn sample_event(currency: &str, impact: Impact) -> EconomicEvent {
+    fn sample_event(currency: Currency, impact: Impact) -> EconomicEvent {
         EconomicEvent {
             name: "Test Event".to_string(),
-            currency: currency.to_string(),
+            currency,
             impact,
-            datetime: Local.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap(),
+            datetime: Utc.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap(),
             actual: None,
             forecast: Some("1.5%".to_string()),
             previous: Some("1.2%".to_string()),
+            is_holiday: false,
+            affected_currencies: None,
         }
     }
 
     #[test]
     fn test_meets_impact() {
-        let high_event = sample_event("USD", Impact::High);
-        let medium_event = sample_event("USD", Impact::Medium);
-        let low_event = sample_event("USD", Impact::Low);
+        let high_event = sample_event(Currency::Usd, Impact::High);
+        let medium_event = sample_event(Currency::Usd, Impact::Medium);
+        let low_event = sample_event(Currency::Usd, Impact::Low);
 
         // High impact meets all levels
         assert!(high_event.meets_impact(Impact::Low));
@@ -86,7 +128,7 @@ mod tests {
 
     #[test]
     fn test_matches_currencies() {
-        let usd_event = sample_event("USD", Impact::High);
+        let usd_event = sample_event(Currency::Usd, Impact::High);
 
         // Empty list matches all
         assert!(usd_event.matches_currencies(&[]));
@@ -103,4 +145,44 @@ mod tests {
         // No match
         assert!(!usd_event.matches_currencies(&["EUR".to_string(), "GBP".to_string()]));
     }
+
+    #[test]
+    fn test_in_timezone_projects_into_target_zone() {
+        let event = sample_event(Currency::Usd, Impact::High);
+        let ny_time = event.in_timezone(chrono_tz::America::New_York);
+        assert_eq!(ny_time.hour(), 8); // 12:00 UTC is 08:00 Eastern (summer, EDT)
+    }
+
+    #[test]
+    fn test_surprise_beats_forecast() {
+        let mut event = sample_event(Currency::Usd, Impact::High);
+        event.actual = Some("240K".to_string());
+        event.forecast = Some("190K".to_string());
+        let surprise = event.surprise().unwrap();
+        assert_eq!(surprise.deviation, 50_000.0);
+        assert_eq!(surprise.direction, crate::types::Direction::Beat);
+    }
+
+    #[test]
+    fn test_surprise_none_without_actual() {
+        let event = sample_event(Currency::Usd, Impact::High);
+        assert!(event.surprise().is_none());
+    }
+
+    #[test]
+    fn test_id_is_stable_and_distinguishes_events() {
+        let event = sample_event(Currency::Usd, Impact::High);
+        assert_eq!(event.id(), sample_event(Currency::Usd, Impact::High).id());
+
+        let other = sample_event(Currency::Eur, Impact::High);
+        assert_ne!(event.id(), other.id());
+    }
+
+    #[test]
+    fn test_id_unaffected_by_actual_value_update() {
+        let mut event = sample_event(Currency::Usd, Impact::High);
+        let id_before = event.id();
+        event.actual = Some("240K".to_string());
+        assert_eq!(event.id(), id_before);
+    }
 }
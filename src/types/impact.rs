@@ -16,7 +16,6 @@ pub enum Impact {
 
 impl Impact {
     /// Convert to star rating (1-3 stars)
-    #[allow(dead_code)]
     pub fn stars(self) -> u8 {
         match self {
             Impact::Low => 1,
@@ -35,6 +34,16 @@ impl Impact {
         }
     }
 
+    /// Colored-circle emoji matching Forex Factory's own impact color coding
+    /// (yellow/orange/red), for use in compact summaries like .ics SUMMARY lines.
+    pub fn emoji(self) -> char {
+        match self {
+            Impact::Low => '🟡',
+            Impact::Medium => '🟠',
+            Impact::High => '🔴',
+        }
+    }
+
     /// Parse from Forex Factory impact class names
     /// e.g., "icon--ff-impact-yel" -> Low, "icon--ff-impact-ora" -> Medium, "icon--ff-impact-red" -> High
     pub fn from_ff_class(class: &str) -> Option<Self> {
@@ -80,6 +89,13 @@ mod tests {
         assert_eq!(Impact::from_stars(4), None);
     }
 
+    #[test]
+    fn test_emoji() {
+        assert_eq!(Impact::Low.emoji(), '🟡');
+        assert_eq!(Impact::Medium.emoji(), '🟠');
+        assert_eq!(Impact::High.emoji(), '🔴');
+    }
+
     #[test]
     fn test_from_ff_class() {
         assert_eq!(
@@ -1,4 +1,5 @@
-use chrono::{DateTime, Local, NaiveDate};
+use chrono::{DateTime, NaiveDate, Utc};
+use chrono_tz::Tz;
 use serde::{Deserialize, Serialize};
 
 use super::Impact;
@@ -19,6 +20,19 @@ pub struct EventQuery {
 
     /// Minimum impact level (defaults to Low if not specified)
     pub min_impact: Option<Impact>,
+
+    /// Timezone events should be displayed/bucketed in (defaults to UTC if
+    /// not specified). Affects rendering and the date boundaries checked by
+    /// [`EventQuery::datetime_in_range`]; storage always uses the canonical
+    /// UTC instant.
+    #[serde(default)]
+    pub display_tz: Option<Tz>,
+
+    /// Drop bank-holiday/market-closure events from the results (see
+    /// [`crate::holidays::HolidayCalendar`]). Defaults to `false`, i.e.
+    /// holidays are included alongside scheduled releases.
+    #[serde(default)]
+    pub exclude_holidays: bool,
 }
 
 impl EventQuery {
@@ -79,9 +93,26 @@ impl EventQuery {
         self
     }
 
-    /// Check if an event's datetime falls within the query date range
-    pub fn datetime_in_range(&self, datetime: &DateTime<Local>) -> bool {
-        let date = datetime.date_naive();
+    /// Set the timezone results should be displayed/bucketed in.
+    pub fn with_display_tz(mut self, tz: Tz) -> Self {
+        self.display_tz = Some(tz);
+        self
+    }
+
+    /// Drop bank-holiday/market-closure events from the results.
+    pub fn with_exclude_holidays(mut self, exclude: bool) -> Self {
+        self.exclude_holidays = exclude;
+        self
+    }
+
+    /// Check if an event's datetime falls within the query date range. The
+    /// date boundary is taken in `display_tz` if set, so range filters line
+    /// up with the dates the caller will actually see rendered.
+    pub fn datetime_in_range(&self, datetime: &DateTime<Utc>) -> bool {
+        let date = match self.display_tz {
+            Some(tz) => datetime.with_timezone(&tz).date_naive(),
+            None => datetime.date_naive(),
+        };
 
         if let Some(from) = self.from_date
             && date < from
@@ -102,7 +133,7 @@ impl EventQuery {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chrono::{Local, TimeZone};
+    use chrono::{TimeZone, Utc};
 
     #[test]
     fn test_with_currency_pair() {
@@ -151,22 +182,22 @@ mod tests {
         );
 
         // Within range
-        let dt = Local.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap();
+        let dt = Utc.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap();
         assert!(query.datetime_in_range(&dt));
 
         // Before range
-        let dt = Local.with_ymd_and_hms(2025, 5, 31, 12, 0, 0).unwrap();
+        let dt = Utc.with_ymd_and_hms(2025, 5, 31, 12, 0, 0).unwrap();
         assert!(!query.datetime_in_range(&dt));
 
         // After range
-        let dt = Local.with_ymd_and_hms(2025, 6, 8, 12, 0, 0).unwrap();
+        let dt = Utc.with_ymd_and_hms(2025, 6, 8, 12, 0, 0).unwrap();
         assert!(!query.datetime_in_range(&dt));
 
         // Boundary dates (inclusive)
-        let dt = Local.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
+        let dt = Utc.with_ymd_and_hms(2025, 6, 1, 0, 0, 0).unwrap();
         assert!(query.datetime_in_range(&dt));
 
-        let dt = Local.with_ymd_and_hms(2025, 6, 7, 23, 59, 59).unwrap();
+        let dt = Utc.with_ymd_and_hms(2025, 6, 7, 23, 59, 59).unwrap();
         assert!(query.datetime_in_range(&dt));
     }
 
@@ -174,7 +205,7 @@ mod tests {
     fn test_datetime_in_range_open_ended() {
         // No constraints
         let query = EventQuery::new();
-        let dt = Local.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap();
+        let dt = Utc.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap();
         assert!(query.datetime_in_range(&dt));
 
         // Only from_date
@@ -182,8 +213,30 @@ mod tests {
             from_date: Some(NaiveDate::from_ymd_opt(2025, 6, 1).unwrap()),
             ..Default::default()
         };
-        assert!(query.datetime_in_range(&Local.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap()));
-        assert!(query.datetime_in_range(&Local.with_ymd_and_hms(2099, 1, 1, 0, 0, 0).unwrap()));
-        assert!(!query.datetime_in_range(&Local.with_ymd_and_hms(2025, 5, 31, 0, 0, 0).unwrap()));
+        assert!(query.datetime_in_range(&Utc.with_ymd_and_hms(2025, 6, 4, 12, 0, 0).unwrap()));
+        assert!(query.datetime_in_range(&Utc.with_ymd_and_hms(2099, 1, 1, 0, 0, 0).unwrap()));
+        assert!(!query.datetime_in_range(&Utc.with_ymd_and_hms(2025, 5, 31, 0, 0, 0).unwrap()));
+    }
+
+    #[test]
+    fn test_datetime_in_range_honors_display_tz_boundary() {
+        let query = EventQuery::new()
+            .with_date_range(
+                NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 6, 1).unwrap(),
+            )
+            .with_display_tz(chrono_tz::America::New_York);
+
+        // 2025-06-01 23:30 UTC is still 2025-06-01 in Eastern (UTC-4 in summer).
+        let dt = Utc.with_ymd_and_hms(2025, 6, 1, 23, 30, 0).unwrap();
+        assert!(query.datetime_in_range(&dt));
+
+        // 2025-06-02 02:00 UTC is 2025-06-01 22:00 Eastern: still in range...
+        let dt = Utc.with_ymd_and_hms(2025, 6, 2, 2, 0, 0).unwrap();
+        assert!(query.datetime_in_range(&dt));
+
+        // ...but 2025-06-02 05:00 UTC is 2025-06-02 01:00 Eastern: out of range.
+        let dt = Utc.with_ymd_and_hms(2025, 6, 2, 5, 0, 0).unwrap();
+        assert!(!query.datetime_in_range(&dt));
     }
 }
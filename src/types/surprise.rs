@@ -0,0 +1,184 @@
+//! Turns the raw `actual`/`forecast` strings scraped from Forex Factory
+//! (e.g. "1.5%", "240K", "-2.3B") into a numeric beat/miss analysis.
+
+use std::fmt;
+
+/// Tolerance below which a deviation is treated as an exact match rather
+/// than a (technically nonzero) beat or miss.
+const IN_LINE_EPSILON: f64 = 1e-9;
+
+/// How the actual value compared to the forecast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// Actual came in above forecast.
+    Beat,
+    /// Actual came in below forecast.
+    Miss,
+    /// Actual matched forecast (within [`IN_LINE_EPSILON`]).
+    InLine,
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Direction::Beat => "beat",
+            Direction::Miss => "miss",
+            Direction::InLine => "in line",
+        };
+        write!(f, "{s}")
+    }
+}
+
+/// Numeric comparison of an event's actual value against its forecast.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Surprise {
+    /// Absolute deviation: `actual - forecast`.
+    pub deviation: f64,
+    /// Deviation as a percentage of the forecast magnitude, when the
+    /// forecast is nonzero (`None` avoids a division by zero).
+    pub pct_deviation: Option<f64>,
+    /// Whether the actual beat, missed, or matched the forecast.
+    pub direction: Direction,
+}
+
+impl fmt::Display for Surprise {
+    /// A short human-readable summary, e.g. "beat forecast by 50000 (+26.32%)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.pct_deviation {
+            Some(pct) => write!(
+                f,
+                "{} forecast by {} ({:+.2}%)",
+                self.direction, self.deviation, pct
+            ),
+            None => write!(f, "{} forecast by {}", self.direction, self.deviation),
+        }
+    }
+}
+
+impl Surprise {
+    /// Compare a parsed actual value against a parsed forecast value.
+    fn from_values(actual: f64, forecast: f64) -> Self {
+        let deviation = actual - forecast;
+        let direction = if deviation.abs() < IN_LINE_EPSILON {
+            Direction::InLine
+        } else if deviation > 0.0 {
+            Direction::Beat
+        } else {
+            Direction::Miss
+        };
+        let pct_deviation = if forecast.abs() < IN_LINE_EPSILON {
+            None
+        } else {
+            Some(deviation / forecast.abs() * 100.0)
+        };
+
+        Self {
+            deviation,
+            pct_deviation,
+            direction,
+        }
+    }
+}
+
+/// Parse a Forex Factory numeric string into an `f64`.
+///
+/// Handles a leading currency symbol, a trailing `%`, comma thousand
+/// separators, and case-insensitive `K`/`M`/`B`/`T` magnitude suffixes
+/// (e.g. "240K" -> 240000.0). Returns `None` for empty or non-numeric
+/// input.
+pub fn parse_numeric(raw: &str) -> Option<f64> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+
+    let without_percent = trimmed.strip_suffix('%').unwrap_or(trimmed);
+    let without_currency = without_percent.trim_start_matches(['$', '€', '£', '¥']);
+    let cleaned: String = without_currency.chars().filter(|c| *c != ',').collect();
+
+    let (number_part, multiplier) = match cleaned.chars().last() {
+        Some(c) if c.eq_ignore_ascii_case(&'k') => (&cleaned[..cleaned.len() - 1], 1_000.0),
+        Some(c) if c.eq_ignore_ascii_case(&'m') => (&cleaned[..cleaned.len() - 1], 1_000_000.0),
+        Some(c) if c.eq_ignore_ascii_case(&'b') => (&cleaned[..cleaned.len() - 1], 1_000_000_000.0),
+        Some(c) if c.eq_ignore_ascii_case(&'t') => {
+            (&cleaned[..cleaned.len() - 1], 1_000_000_000_000.0)
+        }
+        _ => (cleaned.as_str(), 1.0),
+    };
+
+    number_part.trim().parse::<f64>().ok().map(|n| n * multiplier)
+}
+
+/// Compute the surprise between two raw Forex Factory value strings.
+/// Returns `None` if either side is absent or non-numeric.
+pub fn surprise(actual: Option<&str>, forecast: Option<&str>) -> Option<Surprise> {
+    let actual = parse_numeric(actual?)?;
+    let forecast = parse_numeric(forecast?)?;
+    Some(Surprise::from_values(actual, forecast))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_numeric_plain_and_percent() {
+        assert_eq!(parse_numeric("1.5"), Some(1.5));
+        assert_eq!(parse_numeric("1.5%"), Some(1.5));
+        assert_eq!(parse_numeric("-2.3%"), Some(-2.3));
+    }
+
+    #[test]
+    fn test_parse_numeric_magnitude_suffixes() {
+        assert_eq!(parse_numeric("240K"), Some(240_000.0));
+        assert_eq!(parse_numeric("1.2m"), Some(1_200_000.0));
+        assert_eq!(parse_numeric("2.3B"), Some(2_300_000_000.0));
+        assert_eq!(parse_numeric("1t"), Some(1_000_000_000_000.0));
+    }
+
+    #[test]
+    fn test_parse_numeric_currency_symbol_and_commas() {
+        assert_eq!(parse_numeric("$1,200K"), Some(1_200_000.0));
+    }
+
+    #[test]
+    fn test_parse_numeric_rejects_non_numeric() {
+        assert_eq!(parse_numeric(""), None);
+        assert_eq!(parse_numeric("N/A"), None);
+        assert_eq!(parse_numeric("   "), None);
+    }
+
+    #[test]
+    fn test_surprise_beat() {
+        let s = surprise(Some("240K"), Some("190K")).unwrap();
+        assert_eq!(s.deviation, 50_000.0);
+        assert_eq!(s.direction, Direction::Beat);
+    }
+
+    #[test]
+    fn test_surprise_miss() {
+        let s = surprise(Some("1.2%"), Some("1.5%")).unwrap();
+        assert!((s.deviation - (-0.3)).abs() < 1e-9);
+        assert_eq!(s.direction, Direction::Miss);
+    }
+
+    #[test]
+    fn test_surprise_in_line() {
+        let s = surprise(Some("2.0%"), Some("2.0%")).unwrap();
+        assert_eq!(s.direction, Direction::InLine);
+        assert_eq!(s.deviation, 0.0);
+    }
+
+    #[test]
+    fn test_surprise_display() {
+        let s = surprise(Some("240K"), Some("190K")).unwrap();
+        assert_eq!(s.to_string(), "beat forecast by 50000 (+26.32%)");
+    }
+
+    #[test]
+    fn test_surprise_none_when_absent_or_non_numeric() {
+        assert!(surprise(None, Some("1.0")).is_none());
+        assert!(surprise(Some("1.0"), None).is_none());
+        assert!(surprise(Some("N/A"), Some("1.0")).is_none());
+    }
+}
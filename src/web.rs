@@ -0,0 +1,174 @@
+//! Optional HTTP server that renders the Forex Factory calendar as a shareable
+//! HTML page, complementing the stdio MCP transport started by default in
+//! `main.rs`. Opt in with `--http <addr>` or the `FOREX_CALENDAR_HTTP_ADDR`
+//! environment variable.
+
+use std::sync::Arc;
+
+use axum::Router;
+use axum::extract::{Path, State};
+use axum::response::Html;
+use axum::routing::get;
+use chrono::{Datelike, Local, NaiveDate, Weekday};
+use color_eyre::{Result, eyre::eyre};
+
+use crate::service::{CalendarService, DayEvents, group_by_day};
+use crate::types::EventQuery;
+
+/// Start the HTTP calendar server, serving until it shuts down or errors.
+pub async fn serve(addr: &str) -> Result<()> {
+    let service = Arc::new(CalendarService::new()?);
+
+    let app = Router::new()
+        .route("/", get(current_week))
+        .route("/week/{date}", get(week_for_date))
+        .with_state(service);
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .map_err(|e| eyre!("Failed to bind {addr}: {e}"))?;
+
+    axum::serve(listener, app)
+        .await
+        .map_err(|e| eyre!("HTTP server error: {e}"))
+}
+
+async fn current_week(State(service): State<Arc<CalendarService>>) -> Html<String> {
+    render_week(&service, Local::now().date_naive()).await
+}
+
+async fn week_for_date(
+    State(service): State<Arc<CalendarService>>,
+    Path(date): Path<String>,
+) -> Html<String> {
+    let date = NaiveDate::parse_from_str(&date, "%Y-%m-%d").unwrap_or_else(|_| Local::now().date_naive());
+    render_week(&service, date).await
+}
+
+async fn render_week(service: &CalendarService, date: NaiveDate) -> Html<String> {
+    let query = EventQuery::new().with_week_around(date);
+    match service.query_events(&query).await {
+        Ok(events) => Html(render_page(&group_by_day(events, query.display_tz))),
+        Err(e) => Html(format!("<p>Failed to load calendar: {e}</p>")),
+    }
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:2rem;} \
+table{border-collapse:collapse;width:100%;margin-bottom:1.5rem;} \
+th,td{border:1px solid #ccc;padding:0.4rem 0.6rem;text-align:left;} \
+.weekend{opacity:0.6;}";
+
+/// Render day-grouped events as a full HTML page, one block per day with
+/// weekend (Saturday/Sunday) rows visually de-emphasized.
+fn render_page(days: &[DayEvents]) -> String {
+    let mut body = String::new();
+    for day in days {
+        let weekend_class = match day.date.weekday() {
+            Weekday::Sat | Weekday::Sun => " weekend",
+            _ => "",
+        };
+        body.push_str(&format!(
+            "<section class=\"day{weekend_class}\"><h2>{}, {}</h2><table>\
+             <tr><th>Time</th><th>Currency</th><th>Impact</th><th>Event</th><th>Actual</th><th>Forecast</th><th>Previous</th></tr>",
+            day.date.format("%A"),
+            day.date.format("%Y-%m-%d")
+        ));
+        for event in &day.events {
+            let stars = "\u{2605}".repeat(event.impact.stars() as usize);
+            body.push_str(&format!(
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                event.datetime.format("%H:%M"),
+                event.currency,
+                stars,
+                html_escape(&event.name),
+                event.actual.as_deref().map(html_escape).unwrap_or_else(|| "-".to_string()),
+                event.forecast.as_deref().map(html_escape).unwrap_or_else(|| "-".to_string()),
+                event.previous.as_deref().map(html_escape).unwrap_or_else(|| "-".to_string()),
+            ));
+        }
+        body.push_str("</table></section>");
+    }
+
+    format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>Forex Factory Calendar</title>\
+         <style>{STYLE}</style></head><body><h1>Economic Calendar</h1>{body}</body></html>"
+    )
+}
+
+/// Escape the handful of characters that matter when embedding scraped text
+/// into an HTML document.
+fn html_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Currency, EconomicEvent, Impact};
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn test_html_escape() {
+        assert_eq!(html_escape("<b>A & B</b>"), "&lt;b&gt;A &amp; B&lt;/b&gt;");
+    }
+
+    #[test]
+    fn test_render_page_marks_weekend_days() {
+        let event = EconomicEvent {
+            name: "Test".to_string(),
+            currency: Currency::Usd,
+            impact: Impact::High,
+            datetime: Utc.with_ymd_and_hms(2025, 6, 7, 8, 0, 0).unwrap(), // Saturday
+            actual: None,
+            forecast: None,
+            previous: None,
+            is_holiday: false,
+            affected_currencies: None,
+        };
+        let days = group_by_day(vec![event], None);
+        let page = render_page(&days);
+        assert!(page.contains("class=\"day weekend\""));
+        assert!(page.contains("Saturday"));
+    }
+
+    #[test]
+    fn test_render_page_escapes_actual_forecast_previous() {
+        let event = EconomicEvent {
+            name: "Test".to_string(),
+            currency: Currency::Usd,
+            impact: Impact::High,
+            datetime: Utc.with_ymd_and_hms(2025, 6, 9, 8, 0, 0).unwrap(), // Monday
+            actual: Some("<script>alert(1)</script>".to_string()),
+            forecast: Some("<b>1.5%</b>".to_string()),
+            previous: Some("A & B".to_string()),
+            is_holiday: false,
+            affected_currencies: None,
+        };
+        let days = group_by_day(vec![event], None);
+        let page = render_page(&days);
+        assert!(!page.contains("<script>"));
+        assert!(page.contains("&lt;script&gt;"));
+        assert!(page.contains("&lt;b&gt;1.5%&lt;/b&gt;"));
+        assert!(page.contains("A &amp; B"));
+    }
+
+    #[test]
+    fn test_render_page_weekday_has_no_weekend_class() {
+        let event = EconomicEvent {
+            name: "Test".to_string(),
+            currency: Currency::Usd,
+            impact: Impact::Low,
+            datetime: Utc.with_ymd_and_hms(2025, 6, 9, 8, 0, 0).unwrap(), // Monday
+            actual: None,
+            forecast: None,
+            previous: None,
+            is_holiday: false,
+            affected_currencies: None,
+        };
+        let days = group_by_day(vec![event], None);
+        let page = render_page(&days);
+        assert!(page.contains("class=\"day\""));
+    }
+}